@@ -0,0 +1,86 @@
+mod inventory {
+    use variants_data_struct::VariantsDataStruct;
+
+    #[derive(VariantsDataStruct)]
+    #[variants_data_struct(
+        default,
+        accessors,
+        attrs(#[derive(Debug, PartialEq)]),
+        variants_tys_attrs(#[derive(Debug, PartialEq)])
+    )]
+    pub enum Item<T> {
+        #[variants_data_struct_field(field_vis = pub(self))]
+        Count(u32),
+        #[variants_data_struct_field(field_vis = pub(self))]
+        Label(String),
+        #[variants_data_struct_field(field_vis = pub(self))]
+        Extra { value: T },
+    }
+
+    #[derive(VariantsDataStruct)]
+    #[variants_data_struct(default)]
+    pub enum Gauge {
+        Idle,
+        #[variants_data_struct_field(field_vis = pub(self), accessors)]
+        Level(f32),
+    }
+}
+
+use inventory::{
+    CountVariantType, ExtraVariantType, GaugeVariantsData, ItemVariantsData, LabelVariantType,
+};
+
+// Equivalent to (besides the data structs, the variant types and the `Default` implementations):
+//
+// impl<T> ItemVariantsData<T> {
+//     pub fn count(&self) -> &CountVariantType {
+//         &self.count
+//     }
+//
+//     pub fn count_mut(&mut self) -> &mut CountVariantType {
+//         &mut self.count
+//     }
+//
+//     pub fn set_count(&mut self, value: CountVariantType) -> CountVariantType {
+//         core::mem::replace(&mut self.count, value)
+//     }
+//
+//     #[must_use]
+//     pub fn with_count(mut self, value: CountVariantType) -> Self {
+//         self.count = value;
+//         self
+//     }
+//
+//     // ... and the same for `label` and `extra`
+// }
+//
+// impl GaugeVariantsData {
+//     pub fn level(&self) -> &LevelVariantType { ... }
+//     pub fn level_mut(&mut self) -> &mut LevelVariantType { ... }
+//     pub fn set_level(&mut self, value: LevelVariantType) -> LevelVariantType { ... }
+//     pub fn with_level(mut self, value: LevelVariantType) -> Self { ... }
+// }
+
+#[test]
+fn test_accessors() {
+    let mut data = ItemVariantsData::<bool>::default()
+        .with_count(CountVariantType(3))
+        .with_label(LabelVariantType("apple".to_string()));
+    assert_eq!(data.count(), &CountVariantType(3));
+    assert_eq!(data.label(), &LabelVariantType("apple".to_string()));
+
+    data.count_mut().0 += 1;
+    assert_eq!(data.count(), &CountVariantType(4));
+
+    let previous = data.set_extra(ExtraVariantType { value: true });
+    assert_eq!(previous, ExtraVariantType { value: false });
+    assert_eq!(data.extra(), &ExtraVariantType { value: true });
+}
+
+#[test]
+fn test_variant_accessors() {
+    let mut data = GaugeVariantsData::default();
+    data.level_mut().0 = 0.5;
+    assert_eq!(data.level().0, 0.5);
+    assert_eq!(data.idle, ());
+}
@@ -0,0 +1,186 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    builder,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Event<'a, T> {
+    Started,
+    Progress(u8, &'a str),
+    Finished { id: u32, output: T },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct EventVariantsDataBuilder<'a, T> {
+//     pub started: Option<()>,
+//     pub progress: Option<ProgressVariantType<'a>>,
+//     pub finished: Option<FinishedVariantType<T>>,
+// }
+//
+// impl<'a, T> EventVariantsDataBuilder<'a, T> {
+//     pub fn started(self) -> Self { ... }
+//     pub fn set_started(self, value: ()) -> Self { ... }
+//     pub fn progress(self, field_0: u8, field_1: &'a str) -> Self { ... }
+//     pub fn set_progress(self, value: ProgressVariantType<'a>) -> Self { ... }
+//     pub fn finished(
+//         self,
+//         f: impl FnOnce(FinishedVariantTypeBuilder<T, (), ()>) -> FinishedVariantTypeBuilder<T, u32, T>,
+//     ) -> Self { ... }
+//     pub fn set_finished(self, value: FinishedVariantType<T>) -> Self { ... }
+//     pub fn build(self) -> Result<EventVariantsData<'a, T>, EventMissingVariants> { ... }
+// }
+//
+// pub struct FinishedVariantTypeBuilder<T, Id, Output> { pub id: Id, pub output: Output, ... }
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    builder(typestate),
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Signal {
+    Stop,
+    Level(i32),
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct SignalVariantsDataBuilder<Stop, Level> {
+//     pub stop: Stop,
+//     pub level: Level,
+// }
+//
+// impl SignalVariantsDataBuilder<(), LevelVariantType> {
+//     pub fn build(self) -> SignalVariantsData { ... }
+// }
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    builder(typestate),
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Limit {
+    #[variants_data_struct_field(field_ty_override = u64, field_from = u64::from)]
+    Soft(u32),
+    // Without `field_from`, the builder only has the `set_hard(value)` setter
+    #[variants_data_struct_field(field_ty_override = Option<u64>)]
+    Hard(u64),
+}
+
+// Equivalent to (besides the data struct):
+//
+// impl<Soft, Hard> LimitVariantsDataBuilder<Soft, Hard> {
+//     pub fn soft(self, field_0: u32) -> LimitVariantsDataBuilder<u64, Hard> { ... }
+//     pub fn set_soft(self, value: u64) -> LimitVariantsDataBuilder<u64, Hard> { ... }
+//     pub fn set_hard(self, value: Option<u64>) -> LimitVariantsDataBuilder<Soft, Option<u64>> { ... }
+// }
+
+#[test]
+fn test_builder() {
+    let data = EventVariantsData::builder()
+        .started()
+        .progress(50, "halfway")
+        .finished(|b| b.output("done").id(1))
+        .build()
+        .unwrap();
+
+    assert_eq!(
+        data,
+        EventVariantsData {
+            started: (),
+            progress: ProgressVariantType(50, "halfway"),
+            finished: FinishedVariantType {
+                id: 1,
+                output: "done",
+            },
+        }
+    );
+
+    let error = EventVariantsData::<'_, ()>::builder()
+        .set_progress(ProgressVariantType(1, "x"))
+        .build()
+        .unwrap_err();
+    assert_eq!(error.variants, ["Started", "Finished"]);
+}
+
+#[test]
+fn test_typestate_builder() {
+    let data = SignalVariantsData::builder().level(3).build();
+    assert_eq!(
+        data,
+        SignalVariantsData {
+            stop: (),
+            level: LevelVariantType(3),
+        }
+    );
+}
+
+#[test]
+fn test_builder_field_ty_override() {
+    let data = LimitVariantsData::builder().soft(10).set_hard(None).build();
+    assert_eq!(
+        data,
+        LimitVariantsData {
+            soft: 10,
+            hard: None,
+        }
+    );
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    builder(typestate),
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum MyEnum {
+    UnitEnum,
+    TupleEnum(i32, String),
+    StructEnum { id: u32, name: String },
+    // The `builder()` function belongs to the variant type, so the field doesn't conflict with it
+    Nested { builder: String },
+}
+
+// Equivalent to (besides the data struct, the variant types and the typestate parameters):
+//
+// impl MyEnumVariantsDataBuilder {
+//     pub fn tuple_enum(self, field_0: i32, field_1: impl Into<String>) -> ... { ... }
+//     pub fn struct_enum(
+//         self,
+//         f: impl FnOnce(StructEnumVariantTypeBuilder<(), ()>) -> StructEnumVariantTypeBuilder<u32, String>,
+//     ) -> ... { ... }
+//     ...
+// }
+//
+// impl<Id, Name> StructEnumVariantTypeBuilder<Id, Name> {
+//     pub fn id(self, id: u32) -> StructEnumVariantTypeBuilder<u32, Name> { ... }
+//     pub fn name(self, name: impl Into<String>) -> StructEnumVariantTypeBuilder<Id, String> { ... }
+// }
+
+#[test]
+fn test_builder_into_setters() {
+    let data = MyEnumVariantsData::builder()
+        .unit_enum()
+        .tuple_enum(42, "x")
+        .struct_enum(|b| b.id(1).name("n"))
+        .nested(|b| b.builder("b"))
+        .build();
+    assert_eq!(
+        data,
+        MyEnumVariantsData {
+            unit_enum: (),
+            tuple_enum: TupleEnumVariantType(42, "x".to_string()),
+            struct_enum: StructEnumVariantType {
+                id: 1,
+                name: "n".to_string(),
+            },
+            nested: NestedVariantType {
+                builder: "b".to_string(),
+            },
+        }
+    );
+}
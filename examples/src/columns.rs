@@ -0,0 +1,134 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    columns,
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum LogEvent<T> {
+    Start,
+    Sample(T),
+    Message {
+        level: u8,
+        text: String,
+    },
+    #[variants_data_struct_field(gen_variant_ty = false)]
+    Range(T, T),
+}
+
+// Equivalent to (besides the data struct, the variant types and the views):
+//
+// pub struct LogEventColumns<T> {
+//     tags: Vec<u8>,
+//     start: Vec<()>,
+//     sample: Vec<SampleVariantType<T>>,
+//     message: Vec<MessageVariantType>,
+//     range: Vec<(T, T)>,
+// }
+//
+// impl<T> Default for LogEventColumns<T> { ... }
+// impl<T> Extend<LogEvent<T>> for LogEventColumns<T> { ... }
+// impl<T> FromIterator<LogEvent<T>> for LogEventColumns<T> { ... }
+//
+// impl<T> LogEventColumns<T> {
+//     pub fn push(&mut self, value: LogEvent<T>) { ... }
+//     pub fn len(&self) -> usize { ... }
+//     pub fn is_empty(&self) -> bool { ... }
+//     pub fn iter<'a>(&'a self) -> impl Iterator<Item = LogEventRef<'a, T>> + 'a { ... }
+//     pub fn into_enums(self) -> Vec<LogEvent<T>> { ... }
+//     pub fn start(&self) -> &[()] { ... }
+//     pub fn sample(&self) -> &[SampleVariantType<T>] { ... }
+//     pub fn message(&self) -> &[MessageVariantType] { ... }
+//     pub fn range(&self) -> &[(T, T)] { ... }
+// }
+
+fn events() -> Vec<LogEvent<f32>> {
+    vec![
+        LogEvent::Start,
+        LogEvent::Sample(0.5),
+        LogEvent::Message {
+            level: 1,
+            text: "warming up".to_string(),
+        },
+        LogEvent::Sample(1.5),
+        LogEvent::Range(0.0, 2.0),
+    ]
+}
+
+#[test]
+fn test_columns() {
+    let mut columns: LogEventColumns<f32> = events().into_iter().collect();
+    assert_eq!(columns.len(), 5);
+    assert_eq!(
+        columns.sample(),
+        &[SampleVariantType(0.5), SampleVariantType(1.5)]
+    );
+    assert_eq!(columns.range(), &[(0.0, 2.0)]);
+    assert_eq!(columns.start().len(), 1);
+
+    columns.push(LogEvent::Start);
+    assert_eq!(columns.len(), 6);
+    assert_eq!(columns.start().len(), 2);
+
+    let samples: Vec<f32> = columns
+        .iter()
+        .filter_map(|event| match event {
+            LogEventRef::Sample(SampleVariantRef(value)) => Some(*value),
+            LogEventRef::Range(start, end) => Some(end - start),
+            _ => None,
+        })
+        .collect();
+    assert_eq!(samples, vec![0.5, 1.5, 2.0]);
+}
+
+#[test]
+fn test_into_enums() {
+    let columns: LogEventColumns<f32> = events().into_iter().collect();
+    assert_eq!(columns.into_enums(), events());
+    assert!(LogEventColumns::<f32>::default().is_empty());
+}
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    columns,
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum StackOp {
+    // The column named `push` would clash with the `push` method of the columns struct
+    #[variants_data_struct_field(field_name = push_op)]
+    Push(u32),
+    #[variants_data_struct_field(gen_variant_ty = false)]
+    Dup(u32),
+    Pop,
+}
+
+// Equivalent to (besides the data struct, the variant types and the views):
+//
+// pub struct StackOpColumns {
+//     tags: Vec<u8>,
+//     push_op: Vec<PushVariantType>,
+//     dup: Vec<(u32,)>,
+//     pop: Vec<()>,
+// }
+//
+// impl StackOpColumns {
+//     ...
+//     pub fn push_op(&self) -> &[PushVariantType] { ... }
+//     pub fn dup(&self) -> &[(u32,)] { ... }
+//     pub fn pop(&self) -> &[()] { ... }
+// }
+
+#[test]
+fn test_columns_field_names() {
+    let mut columns = StackOpColumns::default();
+    columns.push(StackOp::Push(4));
+    columns.push(StackOp::Dup(4));
+    columns.push(StackOp::Pop);
+    assert_eq!(columns.push_op(), &[PushVariantType(4)]);
+    assert_eq!(columns.dup(), &[(4,)]);
+    assert_eq!(columns.pop().len(), 1);
+    assert_eq!(
+        columns.into_enums(),
+        [StackOp::Push(4), StackOp::Dup(4), StackOp::Pop]
+    );
+}
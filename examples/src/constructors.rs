@@ -0,0 +1,67 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(variants_tys_attrs(#[derive(Debug, PartialEq)]))]
+pub enum Command<T> {
+    Walk(i32, i32),
+    #[variants_data_struct_field(deref = true)]
+    Say(String),
+    Spawn {
+        name: &'static str,
+        payload: T,
+    },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl WalkVariantType {
+//     pub const fn new(field_0: i32, field_1: i32) -> Self { ... }
+//     pub fn into_inner(self) -> (i32, i32) { ... }
+// }
+//
+// impl From<(i32, i32)> for WalkVariantType { ... }
+// impl From<WalkVariantType> for (i32, i32) { ... }
+//
+// impl SayVariantType {
+//     pub const fn new(field_0: String) -> Self { ... }
+//     pub fn into_inner(self) -> String { ... }
+// }
+//
+// impl From<(String,)> for SayVariantType { ... }
+// impl From<SayVariantType> for (String,) { ... }
+// impl Deref for SayVariantType { type Target = String; ... }
+// impl DerefMut for SayVariantType { ... }
+// impl AsRef<String> for SayVariantType { ... }
+//
+// impl<T> SpawnVariantType<T> {
+//     pub const fn new(name: &'static str, payload: T) -> Self { ... }
+// }
+
+const ORIGIN: WalkVariantType = WalkVariantType::new(0, 0);
+
+#[test]
+fn test_constructors() {
+    assert_eq!(ORIGIN, WalkVariantType(0, 0));
+    assert_eq!(
+        SpawnVariantType::new("worker", 7),
+        SpawnVariantType {
+            name: "worker",
+            payload: 7,
+        }
+    );
+
+    let step = WalkVariantType::from((1, -1));
+    assert_eq!(step, WalkVariantType(1, -1));
+    let (x, y): (i32, i32) = step.into();
+    assert_eq!((x, y), (1, -1));
+    assert_eq!(WalkVariantType::new(2, 3).into_inner(), (2, 3));
+}
+
+#[test]
+fn test_deref() {
+    let mut say = SayVariantType::from((String::from("hi"),));
+    say.push('!');
+    assert_eq!(say.len(), 3);
+    assert_eq!(AsRef::<String>::as_ref(&say), "hi!");
+    assert_eq!(say.into_inner(), "hi!");
+}
@@ -0,0 +1,78 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    conversions,
+    variants_tys_attrs(
+        #[derive(Debug, PartialEq)]
+    )
+)]
+pub enum Shape<T> {
+    Empty,
+    Circle(T),
+    Rect {
+        width: T,
+        height: T,
+    },
+    #[variants_data_struct_field(conversions = false)]
+    Label(String),
+    #[variants_data_struct_field(variant_ty_generics(<T>))]
+    Tagged {
+        tag: u8,
+    },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl<T> From<CircleVariantType<T>> for Shape<T> { ... }
+// impl<T> TryFrom<Shape<T>> for CircleVariantType<T> { type Error = Shape<T>; ... }
+//
+// impl<T> From<RectVariantType<T>> for Shape<T> { ... }
+// impl<T> TryFrom<Shape<T>> for RectVariantType<T> { type Error = Shape<T>; ... }
+//
+// impl<T> From<TaggedVariantType<T>> for Shape<T> { ... }
+// impl<T> TryFrom<Shape<T>> for TaggedVariantType<T> { type Error = Shape<T>; ... }
+
+#[test]
+fn test_from_variant_ty() {
+    assert_eq!(Shape::from(CircleVariantType(1.5)), Shape::Circle(1.5));
+    assert_eq!(
+        Shape::from(RectVariantType {
+            width: 2,
+            height: 3
+        }),
+        Shape::Rect {
+            width: 2,
+            height: 3
+        }
+    );
+    assert_eq!(
+        Shape::<()>::from(TaggedVariantType {
+            tag: 4,
+            _marker: ::core::marker::PhantomData,
+        }),
+        Shape::Tagged { tag: 4 }
+    );
+}
+
+#[test]
+fn test_try_from_enum() {
+    assert_eq!(
+        RectVariantType::try_from(Shape::Rect {
+            width: 2,
+            height: 3
+        }),
+        Ok(RectVariantType {
+            width: 2,
+            height: 3
+        })
+    );
+    assert_eq!(
+        CircleVariantType::try_from(Shape::<f64>::Label("label".to_string())),
+        Err(Shape::Label("label".to_string()))
+    );
+    assert_eq!(
+        CircleVariantType::try_from(Shape::<u8>::Empty),
+        Err(Shape::Empty)
+    );
+}
@@ -0,0 +1,67 @@
+use std::thread;
+
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(counters, uniform(attrs(#[derive(Debug, PartialEq)])))]
+pub enum Message<'a, T> {
+    Ping,
+    Text(&'a str),
+    Payload { data: T },
+}
+
+// Equivalent to (besides the data struct, the variant types and the uniform map):
+//
+// pub struct MessageCounters {
+//     pub ping: AtomicU64,
+//     pub text: AtomicU64,
+//     pub payload: AtomicU64,
+// }
+//
+// impl MessageCounters {
+//     pub const fn new() -> Self { ... }
+//     pub fn record<'a, T>(&self, value: &Message<'a, T>) { ... }
+//     pub fn snapshot(&self) -> MessageMap<u64> { ... }
+//     pub fn reset(&self) { ... }
+// }
+//
+// impl Default for MessageCounters { ... }
+
+static COUNTERS: MessageCounters = MessageCounters::new();
+
+#[test]
+fn test_counters() {
+    let counters = MessageCounters::default();
+    counters.record(&Message::<u8>::Ping);
+    counters.record(&Message::<u8>::Text("hello"));
+    counters.record(&Message::Payload { data: 1 });
+    counters.record(&Message::<u8>::Ping);
+
+    assert_eq!(
+        counters.snapshot(),
+        MessageMap {
+            ping: 2,
+            text: 1,
+            payload: 1,
+        }
+    );
+
+    counters.reset();
+    assert_eq!(counters.snapshot(), MessageMap::default());
+}
+
+#[test]
+fn test_counters_across_threads() {
+    thread::scope(|scope| {
+        for _ in 0..4 {
+            scope.spawn(|| {
+                for i in 0..100 {
+                    COUNTERS.record(&Message::Payload { data: i });
+                }
+            });
+        }
+    });
+
+    assert_eq!(COUNTERS.snapshot()[&Message::<()>::Ping], 0);
+    assert_eq!(COUNTERS.snapshot().payload, 400);
+}
@@ -0,0 +1,77 @@
+use std::time::Duration;
+
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq)]
+pub struct Port(u16);
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    default,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Setting<T> {
+    Verbose,
+    Listen(
+        #[variants_data_struct_field(default = Port(8080))] Port,
+        String,
+    ),
+    Retry {
+        #[variants_data_struct_field(default = 3)]
+        attempts: u32,
+        backoff: Duration,
+        extra: T,
+    },
+    #[variants_data_struct_field(field_ty_override = Option<f64>, default = Some(1.5))]
+    Scale(f64),
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl<T> Default for SettingVariantsData<T>
+// where
+//     RetryVariantType<T>: Default,
+// {
+//     fn default() -> Self {
+//         Self {
+//             verbose: Default::default(),
+//             listen: Default::default(),
+//             retry: Default::default(),
+//             scale: Some(1.5),
+//         }
+//     }
+// }
+//
+// impl Default for ListenVariantType {
+//     fn default() -> Self {
+//         Self(Port(8080), Default::default())
+//     }
+// }
+//
+// impl<T> Default for RetryVariantType<T>
+// where
+//     T: Default,
+// {
+//     fn default() -> Self {
+//         Self { attempts: 3, backoff: Default::default(), extra: Default::default() }
+//     }
+// }
+
+#[test]
+fn test_defaults() {
+    let data = SettingVariantsData::<Vec<u8>>::default();
+    assert_eq!(
+        data,
+        SettingVariantsData {
+            verbose: (),
+            listen: ListenVariantType(Port(8080), String::new()),
+            retry: RetryVariantType {
+                attempts: 3,
+                backoff: Duration::ZERO,
+                extra: vec![],
+            },
+            scale: Some(1.5),
+        }
+    );
+}
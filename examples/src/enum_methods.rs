@@ -0,0 +1,87 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    enum_methods,
+    variants_tys_attrs(
+        #[derive(Debug, PartialEq)]
+    )
+)]
+pub enum Command<T> {
+    Quit,
+    Goto {
+        x: T,
+        y: T,
+    },
+    #[variants_data_struct_field(field_name = say, gen_variant_ty = false)]
+    Print(String),
+    #[variants_data_struct_field(gen_variant_ty = false)]
+    Scale(T, T),
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl<T> Command<T> {
+//     pub fn is_quit(&self) -> bool { ... }
+//     pub fn as_quit(&self) -> Option<()> { ... }
+//     pub fn as_quit_mut(&mut self) -> Option<()> { ... }
+//     pub fn into_quit(self) -> Result<(), Self> { ... }
+//     pub fn map_quit(self, f: impl FnOnce(()) -> ()) -> Self { ... }
+//
+//     pub fn is_goto(&self) -> bool { ... }
+//     pub fn as_goto(&self) -> Option<(&T, &T)> { ... }
+//     pub fn as_goto_mut(&mut self) -> Option<(&mut T, &mut T)> { ... }
+//     pub fn into_goto(self) -> Result<GotoVariantType<T>, Self> { ... }
+//     pub fn map_goto(self, f: impl FnOnce(GotoVariantType<T>) -> GotoVariantType<T>) -> Self { ... }
+//
+//     pub fn is_say(&self) -> bool { ... }
+//     pub fn as_say(&self) -> Option<&String> { ... }
+//     pub fn as_say_mut(&mut self) -> Option<&mut String> { ... }
+//     pub fn into_say(self) -> Result<String, Self> { ... }
+//     pub fn map_say(self, f: impl FnOnce(String) -> String) -> Self { ... }
+//
+//     pub fn is_scale(&self) -> bool { ... }
+//     pub fn as_scale(&self) -> Option<(&T, &T)> { ... }
+//     pub fn as_scale_mut(&mut self) -> Option<(&mut T, &mut T)> { ... }
+//     pub fn into_scale(self) -> Result<(T, T), Self> { ... }
+//     pub fn map_scale(self, f: impl FnOnce((T, T)) -> (T, T)) -> Self { ... }
+// }
+
+#[test]
+fn test_is_and_as() {
+    let mut command = Command::Goto { x: 1, y: 2 };
+    assert!(command.is_goto());
+    assert!(!command.is_quit());
+    assert_eq!(command.as_goto(), Some((&1, &2)));
+    assert_eq!(command.as_say(), None);
+
+    if let Some((x, _y)) = command.as_goto_mut() {
+        *x = 10;
+    }
+    assert_eq!(command, Command::Goto { x: 10, y: 2 });
+
+    let command = Command::<u8>::Print("hello".to_string());
+    assert!(command.is_say());
+    assert_eq!(command.as_say(), Some(&"hello".to_string()));
+    assert_eq!(Command::<u8>::Quit.as_quit(), Some(()));
+}
+
+#[test]
+fn test_into_and_map() {
+    assert_eq!(
+        Command::Goto { x: 1, y: 2 }.into_goto(),
+        Ok(GotoVariantType { x: 1, y: 2 })
+    );
+    assert_eq!(Command::<u8>::Quit.into_goto(), Err(Command::Quit));
+    assert_eq!(Command::Scale(2, 3).into_scale(), Ok((2, 3)));
+
+    let command = Command::Goto { x: 1, y: 2 }
+        .map_goto(|GotoVariantType { x, y }| GotoVariantType { x: y, y: x });
+    assert_eq!(command, Command::Goto { x: 2, y: 1 });
+
+    let command = Command::<u8>::Print("hello".to_string()).map_say(|s| s.to_uppercase());
+    assert_eq!(command, Command::Print("HELLO".to_string()));
+
+    let command = Command::<u8>::Quit.map_say(|s| s.to_uppercase());
+    assert_eq!(command, Command::Quit);
+}
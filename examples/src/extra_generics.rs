@@ -0,0 +1,94 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    generics(<U>),
+    where(T: Clone, U: Clone),
+    attrs(
+        #[derive(Clone)]
+    )
+)]
+pub enum Event<T> {
+    #[variants_data_struct_field(
+        variant_ty_where(T: Clone),
+        variant_ty_attrs(
+            #[derive(Clone)]
+        )
+    )]
+    Single(T),
+    #[variants_data_struct_field(field_ty_override = Vec<U>)]
+    Many,
+    #[variants_data_struct_field(
+        variant_ty_generics(<T: Clone>),
+        variant_ty_attrs(
+            #[derive(Clone)]
+        )
+    )]
+    Tagged { tag: u8 },
+}
+
+// Equivalent to:
+// #[derive(Clone)]
+// pub struct EventVariantsData<T, U>
+// where
+//     T: Clone,
+//     U: Clone,
+// {
+//     pub single: SingleVariantType<T>,
+//     pub many: Vec<U>,
+//     pub tagged: TaggedVariantType<T>,
+// }
+//
+// #[derive(Clone)]
+// pub struct SingleVariantType<T>(pub T)
+// where
+//     T: Clone;
+//
+// #[derive(Clone)]
+// pub struct TaggedVariantType<T>
+// where
+//     T: Clone,
+// {
+//     pub tag: u8,
+//     #[doc(hidden)]
+//     pub _marker: ::core::marker::PhantomData<(fn() -> T,)>,
+// }
+
+#[test]
+fn test_extra_generics() {
+    let data_struct: EventVariantsData<String, u32> = EventVariantsData {
+        single: SingleVariantType("single".to_string()),
+        many: vec![1, 2, 3],
+        tagged: TaggedVariantType {
+            tag: 7,
+            _marker: ::core::marker::PhantomData,
+        },
+    };
+    let cloned = data_struct.clone();
+    assert_eq!(cloned.single.0, "single");
+    assert_eq!(cloned.many, [1, 2, 3]);
+    assert_eq!(cloned.tagged.tag, 7);
+}
+
+mod events {
+    use variants_data_struct::VariantsDataStruct;
+
+    #[derive(VariantsDataStruct)]
+    pub enum Tagged<T> {
+        Value(T),
+        #[variants_data_struct_field(variant_ty_generics(<T>))]
+        Labeled {
+            tag: u8,
+        },
+    }
+}
+
+#[test]
+fn test_extra_generics_marker_outside_module() {
+    // The marker field has the visibility of the variant type, so it can be set outside of the defining module
+    let labeled: events::LabeledVariantType<String> = events::LabeledVariantType {
+        tag: 1,
+        _marker: ::core::marker::PhantomData,
+    };
+    assert_eq!(labeled.tag, 1);
+}
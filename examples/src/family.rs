@@ -0,0 +1,97 @@
+use variants_data_struct::VariantsDataStruct;
+use variants_data_struct_runtime::{
+    FamilyMapper, IntoSome, OptionFamily, OwnedFamily, RefFamily, VariantFamily, VecFamily,
+};
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    family,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Shape<T> {
+    Point,
+    Circle(T),
+    Rect { width: T, height: T },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct ShapeVariantsFamily<'f, F: VariantFamily, T>
+// where
+//     T: 'f,
+// {
+//     pub point: F::Apply<'f, ()>,
+//     pub circle: F::Apply<'f, CircleVariantType<T>>,
+//     pub rect: F::Apply<'f, RectVariantType<T>>,
+// }
+//
+// impl<'f, F: VariantFamily, T> ShapeVariantsFamily<'f, F, T> where T: 'f {
+//     pub fn map_family<G: VariantFamily>(self, mapper: impl FamilyMapper<F, G>) -> ShapeVariantsFamily<'f, G, T> { ... }
+// }
+//
+// impl<'f, T> From<ShapeVariantsData<T>> for ShapeVariantsFamily<'f, OwnedFamily, T> where T: 'f { ... }
+// impl<'f, T> From<ShapeVariantsFamily<'f, OwnedFamily, T>> for ShapeVariantsData<T> where T: 'f { ... }
+
+/// Maps the vectors to their first elements.
+struct First;
+
+impl FamilyMapper<VecFamily, OptionFamily> for First {
+    fn map<'a, T: 'a>(
+        &mut self,
+        value: <VecFamily as VariantFamily>::Apply<'a, T>,
+    ) -> <OptionFamily as VariantFamily>::Apply<'a, T> {
+        value.into_iter().next()
+    }
+}
+
+#[test]
+fn test_family() {
+    let data = ShapeVariantsData {
+        point: (),
+        circle: CircleVariantType(1.0),
+        rect: RectVariantType {
+            width: 2.0,
+            height: 3.0,
+        },
+    };
+    let owned: ShapeVariantsFamily<'_, OwnedFamily, f64> = data.into();
+    let options: ShapeVariantsFamily<'_, OptionFamily, f64> = owned.map_family(IntoSome);
+    assert_eq!(options.circle, Some(CircleVariantType(1.0)));
+
+    let circle = CircleVariantType(4.0);
+    let rect = RectVariantType {
+        width: 5.0,
+        height: 6.0,
+    };
+    let refs: ShapeVariantsFamily<'_, RefFamily, f64> = ShapeVariantsFamily {
+        point: &(),
+        circle: &circle,
+        rect: &rect,
+    };
+    assert_eq!(refs.rect.height, 6.0);
+
+    let vecs: ShapeVariantsFamily<'_, VecFamily, f64> = ShapeVariantsFamily {
+        point: vec![(), ()],
+        circle: vec![CircleVariantType(7.0)],
+        rect: vec![],
+    };
+    let firsts = vecs.map_family(First);
+    assert_eq!(firsts.point, Some(()));
+    assert_eq!(firsts.circle, Some(CircleVariantType(7.0)));
+    assert_eq!(firsts.rect, None);
+}
+
+#[test]
+fn test_family_into_data() {
+    let owned: ShapeVariantsFamily<'_, OwnedFamily, u8> = ShapeVariantsFamily {
+        point: (),
+        circle: CircleVariantType(1),
+        rect: RectVariantType {
+            width: 2,
+            height: 3,
+        },
+    };
+    let data: ShapeVariantsData<u8> = owned.into();
+    assert_eq!(data.circle, CircleVariantType(1));
+}
@@ -0,0 +1,200 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    field_wrapper = Option<_>,
+    options,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Setting {
+    Verbose,
+    Threads(u8),
+    #[variants_data_struct_field(field_wrapper = Box<_>)]
+    Path {
+        dir: String,
+        file: String,
+    },
+    #[variants_data_struct_field(field_wrapper = _)]
+    Name(String),
+    #[variants_data_struct_field(field_ty_override = u32, field_from = u32::from)]
+    Limit(u16),
+}
+
+// Equivalent to (besides the variant types and the options struct):
+//
+// #[derive(Debug, PartialEq)]
+// pub struct SettingVariantsData {
+//     pub verbose: Option<()>,
+//     pub threads: Option<ThreadsVariantType>,
+//     pub path: Box<PathVariantType>,
+//     pub name: NameVariantType,
+//     pub limit: Option<u32>,
+// }
+
+#[test]
+fn test_field_wrapper() {
+    let data = SettingVariantsData {
+        verbose: None,
+        threads: Some(ThreadsVariantType(4)),
+        path: Box::new(PathVariantType {
+            dir: "/tmp".to_string(),
+            file: "log".to_string(),
+        }),
+        name: NameVariantType("main".to_string()),
+        limit: Some(10),
+    };
+    assert_eq!(data.threads, Some(ThreadsVariantType(4)));
+}
+
+#[test]
+fn test_field_wrapper_options() {
+    let options: SettingVariantsOptions = [
+        Setting::Verbose,
+        Setting::Threads(4),
+        Setting::Path {
+            dir: "/tmp".to_string(),
+            file: "log".to_string(),
+        },
+        Setting::Name("main".to_string()),
+        Setting::Limit(10),
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(
+        options.try_into_complete(),
+        Ok(SettingVariantsData {
+            verbose: Some(()),
+            threads: Some(ThreadsVariantType(4)),
+            path: Box::new(PathVariantType {
+                dir: "/tmp".to_string(),
+                file: "log".to_string(),
+            }),
+            name: NameVariantType("main".to_string()),
+            limit: Some(10),
+        })
+    );
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    field_wrapper = Vec<_>,
+    field_wrap = |value| vec![value],
+    options,
+    visitor,
+    builder,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Measurement {
+    Missing,
+    Value(i32),
+    Range { low: i32, high: i32 },
+}
+
+// Equivalent to (besides the variant types and the companions):
+//
+// #[derive(Debug, PartialEq)]
+// pub struct MeasurementVariantsData {
+//     pub missing: Vec<()>,
+//     pub value: Vec<ValueVariantType>,
+//     pub range: Vec<RangeVariantType>,
+// }
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    field_wrapper = Vec<_>,
+    field_wrap = |value| vec![value],
+    options,
+    visitor,
+    builder,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Sample {
+    Idle,
+    Reading(i32),
+    // Without `field_wrap`, the value of the field can't be constructed from the variant, so the options
+    // struct has no `try_into_complete`, the enum has no `accept` and the builder has only the `set_*` setter
+    #[variants_data_struct_field(field_wrapper = Vec<Option<_>>)]
+    Label(String),
+}
+
+// Equivalent to (besides the variant types and the companions):
+//
+// #[derive(Debug, PartialEq)]
+// pub struct SampleVariantsData {
+//     pub idle: Vec<()>,
+//     pub reading: Vec<ReadingVariantType>,
+//     pub label: Vec<Option<LabelVariantType>>,
+// }
+
+#[derive(Default)]
+struct Total(i32);
+
+impl MeasurementVariantsVisitor for Total {
+    fn visit_value(&mut self, value: &Vec<ValueVariantType>) {
+        self.0 += value.iter().map(|value| value.0).sum::<i32>();
+    }
+
+    fn visit_range(&mut self, value: &Vec<RangeVariantType>) {
+        self.0 += value
+            .iter()
+            .map(|range| range.high - range.low)
+            .sum::<i32>();
+    }
+}
+
+#[test]
+fn test_field_wrapper_vec() {
+    let complete = MeasurementVariantsData {
+        missing: vec![()],
+        value: vec![ValueVariantType(3)],
+        range: vec![RangeVariantType { low: 1, high: 5 }],
+    };
+
+    let options: MeasurementVariantsOptions = [
+        Measurement::Missing,
+        Measurement::Value(3),
+        Measurement::Range { low: 1, high: 5 },
+    ]
+    .into_iter()
+    .collect();
+    assert_eq!(options.try_into_complete(), Ok(complete));
+
+    let built = MeasurementVariantsData::builder()
+        .missing()
+        .value(3)
+        .range(|b| b.low(1).high(5))
+        .build();
+    assert_eq!(
+        built,
+        Ok(MeasurementVariantsData {
+            missing: vec![()],
+            value: vec![ValueVariantType(3)],
+            range: vec![RangeVariantType { low: 1, high: 5 }],
+        })
+    );
+
+    let mut total = Total::default();
+    Measurement::Value(3).accept(&mut total);
+    Measurement::Range { low: 1, high: 5 }.accept(&mut total);
+    assert_eq!(total.0, 7);
+}
+
+#[test]
+fn test_field_wrapper_vec_without_field_wrap() {
+    let built = SampleVariantsData::builder()
+        .idle()
+        .reading(1)
+        .set_label(vec![None])
+        .build();
+    assert_eq!(
+        built,
+        Ok(SampleVariantsData {
+            idle: vec![()],
+            reading: vec![ReadingVariantType(1)],
+            label: vec![None],
+        })
+    );
+}
@@ -0,0 +1,51 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+pub enum Msg<T: Clone, U, const N: usize>
+where
+    U: Default,
+{
+    Data(T),
+    Batch { items: [U; N] },
+    Pair(T, U),
+    Empty,
+}
+
+// Equivalent to:
+// pub struct MsgVariantsData<T: Clone, U, const N: usize>
+// where
+//     U: Default,
+// {
+//     pub data: DataVariantType<T>,
+//     pub batch: BatchVariantType<U, N>,
+//     pub pair: PairVariantType<T, U>,
+//     pub empty: (),
+// }
+//
+// pub struct DataVariantType<T: Clone>(pub T);
+//
+// pub struct BatchVariantType<U, const N: usize>
+// where
+//     U: Default,
+// {
+//     pub items: [U; N],
+// }
+//
+// pub struct PairVariantType<T: Clone, U>(pub T, pub U)
+// where
+//     U: Default;
+
+#[test]
+fn test_variant_types_generics() {
+    let data: DataVariantType<String> = DataVariantType("Hello".to_string());
+    let batch: BatchVariantType<u8, 2> = BatchVariantType { items: [1, 2] };
+
+    let data_struct: MsgVariantsData<String, u8, 2> = MsgVariantsData {
+        data,
+        batch,
+        pair: PairVariantType("World".to_string(), 3),
+        empty: (),
+    };
+    assert_eq!(data_struct.data.0, "Hello");
+    assert_eq!(data_struct.batch.items, [1, 2]);
+}
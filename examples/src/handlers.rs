@@ -0,0 +1,83 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(handlers)]
+pub enum Input<T> {
+    Key(char),
+    Click { x: i32, y: i32 },
+    Custom(T),
+    Quit,
+}
+
+// Equivalent to (besides the data struct, the variant types and the missing variants error):
+//
+// pub struct InputHandlers<'a, R, T> {
+//     pub key: Box<dyn FnMut(KeyVariantType) -> R + 'a>,
+//     pub click: Box<dyn FnMut(ClickVariantType) -> R + 'a>,
+//     pub custom: Box<dyn FnMut(CustomVariantType<T>) -> R + 'a>,
+//     pub quit: Box<dyn FnMut(()) -> R + 'a>,
+// }
+//
+// impl<'a, R, T> InputHandlers<'a, R, T> {
+//     pub fn new(key: impl FnMut(KeyVariantType) -> R + 'a, ...) -> Self { ... }
+//     pub fn builder() -> InputHandlersBuilder<'a, R, T> { ... }
+//     pub fn handle(&mut self, value: Input<T>) -> R { ... }
+// }
+//
+// impl<'a, R: Default, T> Default for InputHandlers<'a, R, T> { ... }
+//
+// pub struct InputHandlersBuilder<'a, R, T> { ... }
+//
+// impl<'a, R, T> InputHandlersBuilder<'a, R, T> {
+//     pub fn key(self, handler: impl FnMut(KeyVariantType) -> R + 'a) -> Self { ... }
+//     ...
+//     pub fn build(self) -> Result<InputHandlers<'a, R, T>, InputMissingVariants> { ... }
+//     pub fn build_or_default(self) -> InputHandlers<'a, R, T> where R: Default { ... }
+//     pub fn build_or_panic(self) -> InputHandlers<'a, R, T> { ... }
+// }
+
+#[test]
+fn test_handlers() {
+    let mut quits = 0;
+    let mut handlers = InputHandlers::new(
+        |key: KeyVariantType| key.0.to_string(),
+        |click: ClickVariantType| format!("{},{}", click.x, click.y),
+        |custom: CustomVariantType<u8>| custom.0.to_string(),
+        |()| {
+            quits += 1;
+            "quit".to_string()
+        },
+    );
+
+    assert_eq!(handlers.handle(Input::Key('a')), "a");
+    assert_eq!(handlers.handle(Input::Click { x: 1, y: 2 }), "1,2");
+    assert_eq!(handlers.handle(Input::Custom(3)), "3");
+    assert_eq!(handlers.handle(Input::Quit), "quit");
+    drop(handlers);
+    assert_eq!(quits, 1);
+
+    let mut defaults: InputHandlers<'_, u32, ()> = InputHandlers::default();
+    assert_eq!(defaults.handle(Input::Quit), 0);
+}
+
+#[test]
+fn test_handlers_builder() {
+    let builder = InputHandlers::<'_, i32, u8>::builder().click(|click| click.x + click.y);
+    let error = builder.build().err().unwrap();
+    assert_eq!(error.variants, ["Key", "Custom", "Quit"]);
+
+    let mut handlers = InputHandlers::<'_, i32, u8>::builder()
+        .click(|click| click.x + click.y)
+        .build_or_default();
+    assert_eq!(handlers.handle(Input::Click { x: 1, y: 2 }), 3);
+    assert_eq!(handlers.handle(Input::Key('k')), 0);
+
+    let mut handlers = InputHandlers::<'_, i32, u8>::builder()
+        .custom(|custom| custom.0.into())
+        .build_or_panic();
+    assert_eq!(handlers.handle(Input::Custom(7)), 7);
+    let panic = std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| {
+        handlers.handle(Input::Quit)
+    }));
+    assert!(panic.is_err());
+}
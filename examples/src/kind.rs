@@ -0,0 +1,86 @@
+use std::str::FromStr;
+
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(kind)]
+pub enum Request<T> {
+    Get { id: u32 },
+    Put(u32, T),
+    Ping,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord)]
+// pub enum RequestKind {
+//     Get,
+//     Put,
+//     Ping,
+// }
+//
+// impl RequestKind {
+//     pub const ALL: [Self; 3] = [Self::Get, Self::Put, Self::Ping];
+//     pub const COUNT: usize = 3;
+//     pub const fn name(self) -> &'static str { ... }
+// }
+//
+// impl FromStr for RequestKind { type Err = ParseRequestKindError; ... }
+//
+// impl<T> Request<T> {
+//     pub const fn kind(&self) -> RequestKind { ... }
+// }
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(kind(name = Op, vis = pub(crate), derives(VariantsDataStruct), attrs(#[allow(unused)])))]
+#[repr(u8)]
+pub enum Opcode {
+    Nop = 1,
+    Load(u16) = 4,
+    Store(u16),
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, VariantsDataStruct)]
+// #[allow(unused)]
+// #[repr(u8)]
+// pub(crate) enum Op {
+//     Nop = 1,
+//     Load = 4,
+//     Store,
+// }
+//
+// impl Op {
+//     ...
+//     pub(crate) const fn discriminant(self) -> u8 { ... }
+//     pub(crate) fn from_discriminant(discriminant: u8) -> Option<Self> { ... }
+// }
+
+#[test]
+fn test_kind() {
+    assert_eq!(Request::<()>::Get { id: 1 }.kind(), RequestKind::Get);
+    assert_eq!(Request::Put(1, "value").kind(), RequestKind::Put);
+    assert_eq!(
+        RequestKind::ALL,
+        [RequestKind::Get, RequestKind::Put, RequestKind::Ping]
+    );
+    assert_eq!(RequestKind::COUNT, 3);
+    assert_eq!(RequestKind::Ping.name(), "Ping");
+    assert_eq!(RequestKind::from_str("Put"), Ok(RequestKind::Put));
+    assert_eq!(RequestKind::from_str("Post"), Err(ParseRequestKindError));
+}
+
+#[test]
+fn test_kind_discriminants() {
+    assert_eq!(Opcode::Store(2).kind(), Op::Store);
+    assert_eq!(Op::Nop.discriminant(), 1);
+    assert_eq!(Op::Store.discriminant(), 5);
+    assert_eq!(Op::from_discriminant(4), Some(Op::Load));
+    assert_eq!(Op::from_discriminant(2), None);
+    let _: OpVariantsData = OpVariantsData {
+        nop: (),
+        load: (),
+        store: (),
+    };
+}
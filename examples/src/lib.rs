@@ -1,5 +1,32 @@
 #![cfg(test)]
 #![allow(dead_code)]
+#![allow(clippy::needless_pub_self)]
+
+mod accessors;
+mod builder;
+mod columns;
+mod constructors;
+mod conversions;
+mod counters;
+mod defaults;
+mod enum_methods;
+mod extra_generics;
+mod family;
+mod field_wrapper;
+mod generics;
+mod handlers;
+mod kind;
+mod lifetimes;
+mod markers;
+mod options;
+mod record;
+mod reflection;
+mod router;
+mod skip;
+mod traits;
+mod uniform;
+mod views;
+mod visitor;
 
 use variants_data_struct::VariantsDataStruct;
 
@@ -0,0 +1,78 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+pub enum Token<'a, 'b: 'a> {
+    Ident(&'a str),
+    Punct(char),
+    Callback(fn(&str) -> &str),
+    Display {
+        value: &'b dyn std::fmt::Display,
+    },
+    #[variants_data_struct_field(field_ty_override = String)]
+    Literal(&'a str),
+}
+
+// Equivalent to:
+// pub struct TokenVariantsData<'a, 'b: 'a> {
+//     pub ident: IdentVariantType<'a>,
+//     pub punct: PunctVariantType,
+//     pub callback: CallbackVariantType,
+//     pub display: DisplayVariantType<'b>,
+//     pub literal: String,
+// }
+//
+// pub struct IdentVariantType<'a>(pub &'a str);
+//
+// pub struct PunctVariantType(pub char);
+//
+// pub struct CallbackVariantType(pub fn(&str) -> &str);
+//
+// pub struct DisplayVariantType<'b> {
+//     pub value: &'b dyn std::fmt::Display,
+// }
+
+#[derive(VariantsDataStruct)]
+pub enum Overridden<'a, T> {
+    #[variants_data_struct_field(field_ty_override = bool)]
+    Borrowed(&'a T),
+    #[variants_data_struct_field(field_ty_override = u32)]
+    Empty,
+}
+
+// Equivalent to:
+// pub struct OverriddenVariantsData<'a, T> {
+//     pub borrowed: bool,
+//     pub empty: u32,
+//     #[doc(hidden)]
+//     pub _marker: ::core::marker::PhantomData<(&'a (), fn() -> T)>,
+// }
+
+fn trim(s: &str) -> &str {
+    s.trim()
+}
+
+#[test]
+fn test_lifetimes() {
+    let source = String::from(" ident ");
+    let display = 42;
+
+    let data_struct: TokenVariantsData<'_, '_> = TokenVariantsData {
+        ident: IdentVariantType(&source),
+        punct: PunctVariantType(','),
+        callback: CallbackVariantType(trim),
+        display: DisplayVariantType { value: &display },
+        literal: String::from("literal"),
+    };
+    assert_eq!((data_struct.callback.0)(data_struct.ident.0), "ident");
+    assert_eq!(data_struct.display.value.to_string(), "42");
+}
+
+#[test]
+fn test_phantom_marker() {
+    let data_struct: OverriddenVariantsData<'static, String> = OverriddenVariantsData {
+        borrowed: true,
+        empty: 0,
+        _marker: ::core::marker::PhantomData,
+    };
+    assert!(data_struct.borrowed);
+}
@@ -0,0 +1,75 @@
+use variants_data_struct::VariantsDataStruct;
+use variants_data_struct_runtime::{Field, VariantMarker};
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    markers,
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Event<T> {
+    Started,
+    Progress(T),
+    Finished { code: i32 },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub mod event_kinds {
+//     pub struct Started;
+//     pub struct Progress;
+//     pub struct Finished;
+//
+//     impl VariantMarker for Started { const NAME: &'static str = "Started"; }
+//     ...
+// }
+//
+// impl<T> Field<event_kinds::Progress> for EventVariantsData<T> {
+//     type Output = ProgressVariantType<T>;
+//
+//     fn get(&self) -> &Self::Output { &self.progress }
+//     fn get_mut(&mut self) -> &mut Self::Output { &mut self.progress }
+//     fn into_field(self) -> Self::Output { self.progress }
+// }
+// ...
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(markers(mod_name = kinds, attrs(#[allow(unused)])))]
+pub enum Shape {
+    Circle(f64),
+    Square(f64),
+}
+
+fn describe<K: VariantMarker, D: Field<K>>(data: &D) -> (&'static str, &D::Output) {
+    (K::NAME, data.get())
+}
+
+fn replace<K, D: Field<K>>(data: &mut D, value: D::Output) -> D::Output {
+    core::mem::replace(data.get_mut(), value)
+}
+
+#[test]
+fn test_markers() {
+    let mut data = EventVariantsData {
+        started: (),
+        progress: ProgressVariantType(0.5),
+        finished: FinishedVariantType { code: 0 },
+    };
+
+    let (name, progress) = describe::<event_kinds::Progress, _>(&data);
+    assert_eq!(name, "Progress");
+    assert_eq!(*progress, ProgressVariantType(0.5));
+
+    let previous = replace::<event_kinds::Finished, _>(&mut data, FinishedVariantType { code: 1 });
+    assert_eq!(previous, FinishedVariantType { code: 0 });
+    assert_eq!(
+        Field::<event_kinds::Finished>::into_field(data),
+        FinishedVariantType { code: 1 }
+    );
+
+    let shape = ShapeVariantsData {
+        circle: CircleVariantType(1.0),
+        square: SquareVariantType(2.0),
+    };
+    assert_eq!(Field::<kinds::Square>::get(&shape).0, 2.0);
+    assert_eq!(kinds::Circle::NAME, "Circle");
+}
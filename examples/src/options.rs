@@ -0,0 +1,115 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    options,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Reading<T> {
+    Temperature(T),
+    Position {
+        x: T,
+        y: T,
+    },
+    #[variants_data_struct_field(gen_variant_ty = false)]
+    Label(String),
+    #[variants_data_struct_field(field_ty_override = Option<u8>, field_from = Some)]
+    Level(u8),
+    Reset,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct ReadingVariantsOptions<T> {
+//     pub temperature: Option<TemperatureVariantType<T>>,
+//     pub position: Option<PositionVariantType<T>>,
+//     pub label: Option<String>,
+//     pub level: Option<u8>,
+//     pub reset: Option<()>,
+// }
+//
+// impl<T> Default for ReadingVariantsOptions<T> { ... }
+// impl<T> Extend<Reading<T>> for ReadingVariantsOptions<T> { ... }
+// impl<T> FromIterator<Reading<T>> for ReadingVariantsOptions<T> { ... }
+//
+// impl<T> ReadingVariantsOptions<T> {
+//     pub fn insert(&mut self, value: Reading<T>) -> Option<Reading<T>> { ... }
+//     pub fn try_into_complete(self) -> Result<ReadingVariantsData<T>, ReadingMissingVariants> { ... }
+// }
+//
+// pub struct ReadingMissingVariants {
+//     pub variants: Vec<&'static str>,
+// }
+
+#[test]
+fn test_insert() {
+    let mut options = ReadingVariantsOptions::default();
+    assert_eq!(options.insert(Reading::Temperature(20)), None);
+    assert_eq!(
+        options.insert(Reading::Temperature(21)),
+        Some(Reading::Temperature(20))
+    );
+    assert_eq!(options.temperature, Some(TemperatureVariantType(21)));
+    assert_eq!(options.label, None);
+}
+
+#[test]
+fn test_try_into_complete() {
+    let options: ReadingVariantsOptions<i32> = [
+        Reading::Temperature(20),
+        Reading::Label("kitchen".to_string()),
+        Reading::Temperature(21),
+    ]
+    .into_iter()
+    .collect();
+    let err = options.try_into_complete().unwrap_err();
+    assert_eq!(
+        err,
+        ReadingMissingVariants {
+            variants: vec!["Position", "Level", "Reset"],
+        }
+    );
+    assert_eq!(
+        err.to_string(),
+        "missing variants of `Reading`: Position, Level, Reset"
+    );
+
+    let mut options = ReadingVariantsOptions::default();
+    options.extend([
+        Reading::Reset,
+        Reading::Level(3),
+        Reading::Position { x: 1, y: 2 },
+        Reading::Label("kitchen".to_string()),
+        Reading::Temperature(21),
+    ]);
+    assert_eq!(
+        options.try_into_complete(),
+        Ok(ReadingVariantsData {
+            temperature: TemperatureVariantType(21),
+            position: PositionVariantType { x: 1, y: 2 },
+            label: ("kitchen".to_string(),),
+            level: Some(3),
+            reset: (),
+        })
+    );
+}
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(options)]
+pub enum Switch {
+    // Without `field_from`, the `bool` can't be constructed from the variant,
+    // so the options struct has no `try_into_complete` method
+    #[variants_data_struct_field(field_ty_override = bool)]
+    On,
+    Off,
+}
+
+#[test]
+fn test_insert_without_field_from() {
+    let mut options = SwitchVariantsOptions::default();
+    assert_eq!(options.insert(Switch::On), None);
+    assert_eq!(options.insert(Switch::On), Some(Switch::On));
+    assert_eq!(options.on, Some(()));
+    assert_eq!(options.off, None);
+}
@@ -0,0 +1,80 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    record,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+pub enum Stage<T> {
+    Parsed(T),
+    Checked { value: T, ok: bool },
+    Done,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct StageVariantsRecord<
+//     T,
+//     Parsed = ParsedVariantType<T>,
+//     Checked = CheckedVariantType<T>,
+//     Done = (),
+// > {
+//     pub parsed: Parsed,
+//     pub checked: Checked,
+//     pub done: Done,
+//     _marker: PhantomData<(fn() -> T,)>,
+// }
+//
+// impl<T, Parsed, Checked, Done> StageVariantsRecord<T, Parsed, Checked, Done> {
+//     pub fn map_parsed<U>(self, f: impl FnOnce(Parsed) -> U) -> StageVariantsRecord<T, U, Checked, Done> { ... }
+//     pub fn map_checked<U>(self, f: impl FnOnce(Checked) -> U) -> StageVariantsRecord<T, Parsed, U, Done> { ... }
+//     pub fn map_done<U>(self, f: impl FnOnce(Done) -> U) -> StageVariantsRecord<T, Parsed, Checked, U> { ... }
+// }
+//
+// impl<T> From<StageVariantsData<T>> for StageVariantsRecord<T> { ... }
+// impl<T> From<StageVariantsRecord<T>> for StageVariantsData<T> { ... }
+
+#[test]
+fn test_record() {
+    let data = StageVariantsData {
+        parsed: ParsedVariantType("42"),
+        checked: CheckedVariantType {
+            value: "7",
+            ok: true,
+        },
+        done: (),
+    };
+    let record: StageVariantsRecord<&str> = data.into();
+
+    let record: StageVariantsRecord<&str, u32> =
+        record.map_parsed(|parsed| parsed.0.parse().unwrap());
+    assert_eq!(record.parsed, 42);
+    let record: StageVariantsRecord<&str, u32, Option<u32>> =
+        record.map_checked(|checked| checked.ok.then(|| checked.value.parse().unwrap()));
+    assert_eq!(record.checked, Some(7));
+    let record = record.map_done(|()| "done");
+    assert_eq!(record.done, "done");
+}
+
+#[test]
+fn test_record_into_data() {
+    let record = StageVariantsRecord::<u8>::from(StageVariantsData {
+        parsed: ParsedVariantType(1),
+        checked: CheckedVariantType {
+            value: 2,
+            ok: false,
+        },
+        done: (),
+    })
+    .map_parsed(|parsed| ParsedVariantType(parsed.0 + 1));
+    let data: StageVariantsData<u8> = record.into();
+    assert_eq!(data.parsed, ParsedVariantType(2));
+    assert_eq!(
+        data.checked,
+        CheckedVariantType {
+            value: 2,
+            ok: false
+        }
+    );
+}
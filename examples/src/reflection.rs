@@ -0,0 +1,163 @@
+use std::any::type_name;
+use std::collections::HashMap;
+use variants_data_struct::VariantsDataStruct;
+
+use variants_data_struct_runtime::{FieldInfo, VariantShape};
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(reflection)]
+#[repr(u8)]
+pub enum Config<T> {
+    Disabled = 1,
+    Fixed(T, u32),
+    Auto { min: u32, max: Vec<T> } = 10,
+    Manual,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl<T> Config<T> {
+//     pub const VARIANTS: &'static [VariantInfo] = &[
+//         VariantInfo {
+//             name: "Disabled",
+//             field_name: "disabled",
+//             variant_ty_name: None,
+//             shape: VariantShape::Unit,
+//             fields: &[],
+//             discriminant: 1,
+//         },
+//         VariantInfo {
+//             name: "Fixed",
+//             field_name: "fixed",
+//             variant_ty_name: Some("FixedVariantType"),
+//             shape: VariantShape::Tuple,
+//             fields: &[
+//                 FieldInfo { name: "0", type_name_fn: type_name::<T> },
+//                 FieldInfo { name: "1", type_name_fn: type_name::<u32> },
+//             ],
+//             discriminant: 2,
+//         },
+//         ...
+//     ];
+// }
+//
+// impl<T> ConfigVariantsData<T> {
+//     pub fn field_names() -> &'static [&'static str] { ... }
+//     pub fn get_by_name(&self, name: &str) -> Option<&dyn Any> where Self: 'static { ... }
+// }
+
+#[test]
+fn test_reflection() {
+    let variants = Config::<u8>::VARIANTS;
+    assert_eq!(variants.len(), 4);
+
+    assert_eq!(variants[0].name, "Disabled");
+    assert_eq!(variants[0].variant_ty_name, None);
+    assert_eq!(variants[0].shape, VariantShape::Unit);
+    assert_eq!(variants[0].discriminant, 1);
+
+    assert_eq!(variants[1].field_name, "fixed");
+    assert_eq!(variants[1].variant_ty_name, Some("FixedVariantType"));
+    assert_eq!(
+        variants[1].fields,
+        [
+            FieldInfo {
+                name: "0",
+                type_name_fn: type_name::<u8>,
+            },
+            FieldInfo {
+                name: "1",
+                type_name_fn: type_name::<u32>,
+            },
+        ]
+    );
+    assert_eq!(variants[1].fields[0].type_name(), "u8");
+    assert_eq!(variants[1].discriminant, 2);
+
+    assert_eq!(variants[2].shape, VariantShape::Struct);
+    assert_eq!(variants[2].fields[0].name, "min");
+    assert_eq!(variants[2].discriminant, 10);
+    assert_eq!(variants[3].discriminant, 11);
+}
+
+#[test]
+fn test_get_by_name() {
+    let data = ConfigVariantsData {
+        disabled: (),
+        fixed: FixedVariantType(1u8, 2),
+        auto: AutoVariantType {
+            min: 3,
+            max: vec![4],
+        },
+        manual: (),
+    };
+
+    assert_eq!(
+        ConfigVariantsData::<u8>::field_names(),
+        ["disabled", "fixed", "auto", "manual"]
+    );
+    let fixed = data
+        .get_by_name("fixed")
+        .and_then(|fixed| fixed.downcast_ref::<FixedVariantType<u8>>())
+        .unwrap();
+    assert_eq!(fixed.1, 2);
+    assert!(data.get_by_name("unknown").is_none());
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(reflection)]
+pub enum Lookup<'a, T> {
+    Table(HashMap<u8, Option<T>>, &'a mut [u8; 4]),
+    Filter {
+        name: &'static str,
+        f: Box<dyn Fn(&T) -> bool + Send + 'a>,
+        key: (u8,),
+    },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// impl<'a, T> Lookup<'a, T> {
+//     pub const VARIANTS: &'static [VariantInfo] = &[
+//         VariantInfo {
+//             name: "Table",
+//             fields: &[
+//                 FieldInfo { name: "0", type_name_fn: type_name::<HashMap<u8, Option<T>>> },
+//                 FieldInfo { name: "1", type_name_fn: type_name::<&'a mut [u8; 4]> },
+//             ],
+//             ...
+//         },
+//         ...
+//     ];
+// }
+
+#[test]
+fn test_reflection_type_names() {
+    let type_names = |variant: usize| {
+        Lookup::<'static, u8>::VARIANTS[variant]
+            .fields
+            .iter()
+            .map(FieldInfo::type_name)
+            .collect::<Vec<_>>()
+    };
+    // The generic parameters of the enum are substituted
+    assert_eq!(
+        type_names(0),
+        [
+            type_name::<HashMap<u8, Option<u8>>>(),
+            type_name::<&mut [u8; 4]>()
+        ]
+    );
+    assert_eq!(
+        type_names(1),
+        [
+            type_name::<&str>(),
+            type_name::<Box<dyn Fn(&u8) -> bool + Send>>(),
+            type_name::<(u8,)>()
+        ]
+    );
+    assert_eq!(
+        Config::<u8>::VARIANTS[2].fields[1].type_name(),
+        type_name::<Vec<u8>>()
+    );
+}
@@ -0,0 +1,106 @@
+use std::thread;
+
+use variants_data_struct::VariantsDataStruct;
+use variants_data_struct_runtime::RouteErrorReason;
+
+#[derive(VariantsDataStruct, Debug, PartialEq)]
+#[variants_data_struct(router, variants_tys_attrs(#[derive(Debug, PartialEq)]))]
+pub enum Job<T> {
+    Resize { width: u32, height: u32 },
+    Compress(T),
+    Shutdown,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub struct JobRouter<T> {
+//     pub resize: Sender<ResizeVariantType>,
+//     pub compress: Sender<CompressVariantType<T>>,
+//     pub shutdown: Sender<()>,
+// }
+//
+// pub struct JobReceivers<T> {
+//     pub resize: Receiver<ResizeVariantType>,
+//     pub compress: Receiver<CompressVariantType<T>>,
+//     pub shutdown: Receiver<()>,
+// }
+//
+// impl<T> JobRouter<T> {
+//     pub fn channel() -> (Self, JobReceivers<T>) { ... }
+//     pub fn route(&self, value: Job<T>) -> Result<(), RouteError<Job<T>>> { ... }
+// }
+//
+// impl<T> Clone for JobRouter<T> { ... }
+
+#[test]
+fn test_router() {
+    let (router, receivers) = JobRouter::<Vec<u8>>::channel();
+    let JobReceivers {
+        resize,
+        compress,
+        shutdown,
+    } = receivers;
+
+    let worker = thread::spawn(move || compress.iter().map(|job| job.0.len()).sum::<usize>());
+
+    router
+        .route(Job::Resize {
+            width: 1,
+            height: 2,
+        })
+        .unwrap();
+    let cloned = router.clone();
+    cloned.route(Job::Compress(vec![1, 2, 3])).unwrap();
+    router.route(Job::Compress(vec![4])).unwrap();
+    router.route(Job::Shutdown).unwrap();
+    drop((router, cloned));
+
+    assert_eq!(worker.join().unwrap(), 4);
+    assert_eq!(
+        resize.try_recv(),
+        Ok(ResizeVariantType {
+            width: 1,
+            height: 2
+        })
+    );
+    assert_eq!(shutdown.try_recv(), Ok(()));
+}
+
+#[test]
+fn test_route_error() {
+    let (router, receivers) = JobRouter::<u8>::channel();
+    drop(receivers.compress);
+
+    let error = router.route(Job::Compress(1)).unwrap_err();
+    assert_eq!(error.variant, "Compress");
+    assert_eq!(error.reason, RouteErrorReason::Disconnected);
+    assert_eq!(
+        error.to_string(),
+        "the receiver of `Compress` is disconnected"
+    );
+    assert_eq!(error.into_inner(), Job::Compress(1));
+}
+
+#[derive(VariantsDataStruct, Debug, PartialEq)]
+#[variants_data_struct(router)]
+pub enum Command {
+    Run(u32),
+    #[variants_data_struct_field(skip)]
+    Debug(String),
+}
+
+#[test]
+fn test_route_error_skipped() {
+    let (router, receivers) = CommandRouter::channel();
+    router.route(Command::Run(1)).unwrap();
+    assert_eq!(receivers.run.recv().unwrap().0, 1);
+
+    let error = router.route(Command::Debug("x".to_string())).unwrap_err();
+    assert_eq!(error.variant, "Debug");
+    assert_eq!(error.reason, RouteErrorReason::Skipped);
+    assert_eq!(
+        error.to_string(),
+        "the variant `Debug` is skipped and has no receiver"
+    );
+    assert_eq!(error.into_inner(), Command::Debug("x".to_string()));
+}
@@ -0,0 +1,142 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(Debug, PartialEq, VariantsDataStruct)]
+#[variants_data_struct(
+    exclude_units,
+    except(Raw),
+    kind,
+    columns,
+    options,
+    counters,
+    conversions,
+    attrs(#[derive(Debug, PartialEq)]),
+    variants_tys_attrs(#[derive(Debug, PartialEq)])
+)]
+#[repr(u8)]
+pub enum Packet {
+    Ping = 1,
+    Data(Vec<u8>),
+    #[variants_data_struct_field(skip)]
+    Unknown(u8),
+    Ack {
+        id: u32,
+    },
+    Raw(String),
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(only(Press))]
+pub enum Input {
+    Press(char),
+    Quit,
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(kind)]
+pub enum Key {
+    #[variants_data_struct_field(skip)]
+    Tab,
+    Enter,
+    Escape,
+}
+
+// Equivalent to (besides the variant types and the companions):
+//
+// pub struct PacketVariantsData {
+//     pub data: DataVariantType,
+//     pub ack: AckVariantType,
+// }
+//
+// #[repr(u8)]
+// pub enum PacketKind {
+//     Data = (1) + 1,
+//     Ack = (1) + 3,
+// }
+//
+// impl Packet {
+//     pub const fn kind(&self) -> Option<PacketKind> { ... }
+// }
+//
+// impl PacketColumns {
+//     pub fn push(&mut self, value: Packet) -> Result<(), Packet> { ... }
+// }
+//
+// impl PacketVariantsOptions {
+//     pub fn insert(&mut self, value: Packet) -> Result<Option<Packet>, Packet> { ... }
+// }
+//
+// pub enum KeyKind {
+//     Enter = 1,
+//     Escape = 2,
+// }
+//
+// pub struct InputVariantsData {
+//     pub press: PressVariantType,
+// }
+
+#[test]
+fn test_skip() {
+    let data = PacketVariantsData {
+        data: DataVariantType(vec![1, 2]),
+        ack: AckVariantType { id: 7 },
+    };
+    assert_eq!(data.ack, AckVariantType { id: 7 });
+
+    assert_eq!(
+        Packet::Data(vec![]).kind().map(PacketKind::discriminant),
+        Some(2)
+    );
+    assert_eq!(
+        Packet::Ack { id: 0 }.kind().map(PacketKind::discriminant),
+        Some(4)
+    );
+    assert_eq!(Packet::Unknown(3).kind(), None);
+    assert_eq!(Packet::Ping.kind(), None);
+    assert_eq!(PacketKind::COUNT, 2);
+
+    assert_eq!(
+        DataVariantType::try_from(Packet::Raw("raw".to_string())),
+        Err(Packet::Raw("raw".to_string()))
+    );
+
+    let _data = InputVariantsData {
+        press: PressVariantType('q'),
+    };
+}
+
+#[test]
+fn test_skip_companions() {
+    let mut columns = PacketColumns::default();
+    assert_eq!(columns.push(Packet::Ack { id: 1 }), Ok(()));
+    assert_eq!(columns.push(Packet::Unknown(9)), Err(Packet::Unknown(9)));
+    columns.extend([Packet::Ping, Packet::Data(vec![3])]);
+    assert_eq!(columns.len(), 2);
+
+    let mut options = PacketVariantsOptions::default();
+    assert_eq!(options.insert(Packet::Ack { id: 1 }), Ok(None));
+    assert_eq!(options.insert(Packet::Ping), Err(Packet::Ping));
+
+    let counters = PacketCounters::new();
+    counters.record(&Packet::Ack { id: 1 });
+    counters.record(&Packet::Raw("raw".to_string()));
+    assert_eq!(counters.snapshot().ack, 1);
+    assert_eq!(counters.snapshot().data, 0);
+}
+
+// The fallback for the `discriminant` method, which the kind enum doesn't have without explicit discriminants
+trait NoDiscriminant: Sized {
+    fn discriminant(self) -> Option<isize> {
+        None
+    }
+}
+
+impl NoDiscriminant for KeyKind {}
+
+#[test]
+fn test_skip_implicit_discriminants() {
+    assert_eq!(KeyKind::Enter as isize, 1);
+    assert_eq!(KeyKind::Escape as isize, 2);
+    assert_eq!(KeyKind::Enter.discriminant(), None);
+    assert_eq!(Key::Tab.kind(), None);
+    assert_eq!(Key::Escape.kind(), Some(KeyKind::Escape));
+}
@@ -0,0 +1,97 @@
+use variants_data_struct::VariantsDataStruct;
+use variants_data_struct_runtime::{self as runtime, HasVariantsData, VariantOf};
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(traits, crate = runtime)]
+pub enum Packet<'a, T> {
+    Ping,
+    Data(&'a [u8], T),
+    Close { reason: &'a str },
+}
+
+// Equivalent to (besides the data struct, the variant types and the kind enum):
+//
+// impl<'a, T> HasVariantsData for Packet<'a, T> {
+//     type Data = PacketVariantsData<'a, T>;
+//     type Kind = PacketKind;
+//     const VARIANT_COUNT: usize = 3;
+//
+//     fn kind(&self) -> Self::Kind { Packet::kind(self) }
+// }
+//
+// impl<'a, T> VariantOf<Packet<'a, T>> for DataVariantType<'a, T> {
+//     const INDEX: usize = 1;
+//
+//     fn into_enum(self) -> Packet<'a, T> { ... }
+// }
+//
+// impl<'a, T> VariantOf<Packet<'a, T>> for CloseVariantType<'a> {
+//     const INDEX: usize = 2;
+//
+//     fn into_enum(self) -> Packet<'a, T> { ... }
+// }
+
+fn variant_count<E: HasVariantsData>() -> usize {
+    E::VARIANT_COUNT
+}
+
+fn kinds_of<E: HasVariantsData>(values: &[E]) -> Vec<E::Kind> {
+    values.iter().map(HasVariantsData::kind).collect()
+}
+
+fn index_and_enum<E, V: VariantOf<E>>(value: V) -> (usize, E) {
+    (V::INDEX, value.into_enum())
+}
+
+#[test]
+fn test_traits() {
+    assert_eq!(variant_count::<Packet<'_, u8>>(), 3);
+
+    let (index, packet) = index_and_enum(CloseVariantType::<'_> { reason: "done" });
+    assert_eq!(index, 2);
+    let packet: Packet<'_, u8> = packet;
+    assert!(matches!(packet, Packet::Close { reason: "done" }));
+
+    let (index, packet) = index_and_enum::<Packet<'_, u8>, _>(DataVariantType(&[1, 2], 3));
+    assert_eq!(index, 1);
+    assert_eq!(
+        kinds_of(&[Packet::Ping, packet]),
+        [PacketKind::Ping, PacketKind::Data]
+    );
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(traits, crate = runtime)]
+pub enum Frame {
+    #[variants_data_struct_field(skip)]
+    Padding,
+    Text(String),
+    Binary(Vec<u8>),
+}
+
+// Equivalent to (besides the data struct, the variant types and the kind enum):
+//
+// impl HasVariantsData for Frame {
+//     type Data = FrameVariantsData;
+//     type Kind = Option<FrameKind>;
+//     const VARIANT_COUNT: usize = 2;
+//
+//     fn kind(&self) -> Self::Kind { Frame::kind(self) }
+// }
+//
+// impl VariantOf<Frame> for TextVariantType {
+//     const INDEX: usize = 0;
+//     ...
+// }
+
+#[test]
+fn test_traits_skipped_variants() {
+    assert_eq!(variant_count::<Frame>(), 2);
+
+    let (index, frame) = index_and_enum(BinaryVariantType(vec![1]));
+    assert_eq!(index, 1);
+    assert_eq!(
+        kinds_of(&[Frame::Padding, frame]),
+        [None, Some(FrameKind::Binary)]
+    );
+}
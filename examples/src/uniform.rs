@@ -0,0 +1,70 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(uniform(attrs(#[derive(Debug, PartialEq)])))]
+pub enum Metric<'a, T> {
+    Requests,
+    Latency(T),
+    Error { code: u16, message: &'a str },
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// #[derive(Debug, PartialEq)]
+// pub struct MetricMap<T> {
+//     pub requests: T,
+//     pub latency: T,
+//     pub error: T,
+// }
+//
+// impl<T: Default> Default for MetricMap<T> { ... }
+// impl<'a, T, T1> Index<&Metric<'a, T>> for MetricMap<T1> { type Output = T1; ... }
+// impl<'a, T, T1> IndexMut<&Metric<'a, T>> for MetricMap<T1> { ... }
+//
+// impl<T> MetricMap<T> {
+//     pub fn from_fn(f: impl FnMut(&'static str) -> T) -> Self { ... }
+//     pub fn map<U>(self, f: impl FnMut(T) -> U) -> MetricMap<U> { ... }
+//     pub fn zip<U>(self, other: MetricMap<U>) -> MetricMap<(T, U)> { ... }
+//     pub fn iter(&self) -> impl Iterator<Item = (&'static str, &T)> { ... }
+// }
+
+#[test]
+fn test_index() {
+    let mut counts = MetricMap::<u32>::default();
+    for metric in [
+        Metric::Requests,
+        Metric::Latency(1.5),
+        Metric::Requests,
+        Metric::Error {
+            code: 500,
+            message: "internal",
+        },
+    ] {
+        counts[&metric] += 1;
+    }
+    assert_eq!(
+        counts,
+        MetricMap {
+            requests: 2,
+            latency: 1,
+            error: 1,
+        }
+    );
+    assert_eq!(counts[&Metric::<()>::Requests], 2);
+}
+
+#[test]
+fn test_from_fn_map_zip_iter() {
+    let names = MetricMap::from_fn(|name| name.to_lowercase());
+    let lens = names.map(|name| name.len());
+    let zipped = MetricMap::from_fn(|name| name).zip(lens);
+    assert_eq!(zipped.latency, ("Latency", 7));
+    assert_eq!(
+        zipped.iter().map(|(name, _)| name).collect::<Vec<_>>(),
+        vec!["Requests", "Latency", "Error"]
+    );
+    assert_eq!(
+        zipped.iter().map(|(_, (_, len))| *len).sum::<usize>(),
+        8 + 7 + 5
+    );
+}
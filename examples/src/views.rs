@@ -0,0 +1,98 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(
+    views(
+        attrs(
+            #[derive(Debug)]
+        )
+    )
+)]
+pub enum Node<'s, T> {
+    Leaf,
+    Value(T),
+    Named {
+        name: &'s str,
+        weight: u32,
+    },
+    #[variants_data_struct_field(gen_variant_ty = false)]
+    Pair(T, T),
+    #[variants_data_struct_field(gen_variant_ty = true)]
+    Marker,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// #[derive(Debug)]
+// pub enum NodeRef<'a, 's, T> {
+//     Leaf,
+//     Value(ValueVariantRef<'a, T>),
+//     Named(NamedVariantRef<'a, 's>),
+//     Pair(&'a T, &'a T),
+//     Marker(MarkerVariantRef<'a>),
+// }
+//
+// #[derive(Debug)]
+// pub struct ValueVariantRef<'a, T>(pub &'a T);
+//
+// #[derive(Debug)]
+// pub struct NamedVariantRef<'a, 's> {
+//     pub name: &'a &'s str,
+//     pub weight: &'a u32,
+// }
+//
+// #[derive(Debug)]
+// pub struct MarkerVariantRef<'a> {
+//     #[doc(hidden)]
+//     pub _marker: ::core::marker::PhantomData<(&'a (),)>,
+// }
+//
+// ... and the same for `NodeMut<'a, 's, T>` with `&'a mut` references.
+//
+// impl<'s, T> Node<'s, T> {
+//     pub fn as_variant_ref<'a>(&'a self) -> NodeRef<'a, 's, T> { ... }
+//     pub fn as_variant_mut<'a>(&'a mut self) -> NodeMut<'a, 's, T> { ... }
+// }
+
+fn weight(view: NamedVariantRef<'_, '_>) -> u32 {
+    *view.weight
+}
+
+#[test]
+fn test_as_variant_ref() {
+    let node: Node<'_, i32> = Node::Named {
+        name: "root",
+        weight: 3,
+    };
+    match node.as_variant_ref() {
+        NodeRef::Named(named) => {
+            let copied = named;
+            assert_eq!(*named.name, "root");
+            assert_eq!(weight(copied), 3);
+        }
+        other => panic!("unexpected view: {other:?}"),
+    }
+
+    let node: Node<'_, i32> = Node::Pair(1, 2);
+    assert!(matches!(node.as_variant_ref(), NodeRef::Pair(&1, &2)));
+    assert!(matches!(Node::<i32>::Leaf.as_variant_ref(), NodeRef::Leaf));
+    assert!(matches!(
+        Node::<i32>::Marker.as_variant_ref(),
+        NodeRef::Marker(_)
+    ));
+}
+
+#[test]
+fn test_as_variant_mut() {
+    let mut node: Node<'_, String> = Node::Value("a".to_string());
+    if let NodeMut::Value(value) = node.as_variant_mut() {
+        value.0.push('b');
+    }
+    assert!(matches!(&node, Node::Value(value) if value == "ab"));
+
+    let mut node: Node<'_, String> = Node::Pair("x".to_string(), "y".to_string());
+    if let NodeMut::Pair(first, second) = node.as_variant_mut() {
+        std::mem::swap(first, second);
+    }
+    assert!(matches!(&node, Node::Pair(first, second) if first == "y" && second == "x"));
+}
@@ -0,0 +1,144 @@
+use variants_data_struct::VariantsDataStruct;
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(visitor)]
+pub enum Node<T> {
+    Leaf(T),
+    Pair { left: T, right: T },
+    Guest { visitor: String },
+    Empty,
+}
+
+// Equivalent to (besides the data struct and the variant types):
+//
+// pub trait NodeVariantsVisitor<T> {
+//     fn visit_leaf(&mut self, value: &LeafVariantType<T>) {}
+//     fn visit_pair(&mut self, value: &PairVariantType<T>) {}
+//     fn visit_guest(&mut self, value: &GuestVariantType) {}
+//     fn visit_empty(&mut self, value: &()) {}
+// }
+//
+// pub trait NodeVariantsVisitorMut<T> {
+//     fn visit_leaf_mut(&mut self, value: &mut LeafVariantType<T>) {}
+//     ...
+// }
+//
+// impl<T> NodeVariantsData<T> {
+//     pub fn accept(&self, visitor: &mut impl NodeVariantsVisitor<T>) { ... }
+//     pub fn accept_mut(&mut self, visitor: &mut impl NodeVariantsVisitorMut<T>) { ... }
+// }
+//
+// impl<T> Node<T> {
+//     pub fn accept<V: NodeVariantsVisitor<T>>(self, visitor: &mut V) { ... }
+// }
+
+#[derive(Default)]
+struct Sum {
+    total: i32,
+    visited: Vec<&'static str>,
+}
+
+impl NodeVariantsVisitor<i32> for Sum {
+    fn visit_leaf(&mut self, value: &LeafVariantType<i32>) {
+        self.total += value.0;
+        self.visited.push("leaf");
+    }
+
+    fn visit_pair(&mut self, value: &PairVariantType<i32>) {
+        self.total += value.left + value.right;
+        self.visited.push("pair");
+    }
+
+    fn visit_guest(&mut self, value: &GuestVariantType) {
+        self.visited.push(if value.visitor.is_empty() {
+            "anonymous guest"
+        } else {
+            "guest"
+        });
+    }
+}
+
+struct Double;
+
+impl NodeVariantsVisitorMut<i32> for Double {
+    fn visit_leaf_mut(&mut self, value: &mut LeafVariantType<i32>) {
+        value.0 *= 2;
+    }
+}
+
+#[test]
+fn test_visitor() {
+    let mut data = NodeVariantsData {
+        leaf: LeafVariantType(1),
+        pair: PairVariantType { left: 2, right: 3 },
+        guest: GuestVariantType {
+            visitor: "Ann".to_string(),
+        },
+        empty: (),
+    };
+
+    data.accept_mut(&mut Double);
+    let mut sum = Sum::default();
+    data.accept(&mut sum);
+    assert_eq!(sum.total, 7);
+    assert_eq!(sum.visited, ["leaf", "pair", "guest"]);
+}
+
+#[test]
+fn test_enum_accept() {
+    let mut sum = Sum::default();
+    Node::Pair { left: 4, right: 5 }.accept(&mut sum);
+    Node::<i32>::Guest {
+        visitor: String::new(),
+    }
+    .accept(&mut sum);
+    Node::Empty.accept(&mut sum);
+    assert_eq!(sum.total, 9);
+    assert_eq!(sum.visited, ["pair", "anonymous guest"]);
+}
+
+#[derive(VariantsDataStruct)]
+#[variants_data_struct(visitor)]
+pub enum Metric {
+    #[variants_data_struct_field(field_ty_override = u64, field_from = u64::from)]
+    Bytes(u32),
+    #[variants_data_struct_field(field_ty_override = f64, field_from = |(num, den)| num as f64 / den as f64)]
+    Ratio(u8, u8),
+}
+
+// Equivalent to (besides the data struct):
+//
+// pub trait MetricVariantsVisitor {
+//     fn visit_bytes(&mut self, value: &u64) {}
+//     fn visit_ratio(&mut self, value: &f64) {}
+// }
+//
+// impl Metric {
+//     pub fn accept<V: MetricVariantsVisitor>(self, visitor: &mut V) { ... }
+// }
+
+#[derive(Default)]
+struct Report {
+    bytes: u64,
+    ratios: Vec<f64>,
+}
+
+impl MetricVariantsVisitor for Report {
+    fn visit_bytes(&mut self, value: &u64) {
+        self.bytes += value;
+    }
+
+    fn visit_ratio(&mut self, value: &f64) {
+        self.ratios.push(*value);
+    }
+}
+
+#[test]
+fn test_enum_accept_field_from() {
+    let mut report = Report::default();
+    Metric::Bytes(u32::MAX).accept(&mut report);
+    Metric::Bytes(1).accept(&mut report);
+    Metric::Ratio(1, 4).accept(&mut report);
+    assert_eq!(report.bytes, u64::from(u32::MAX) + 1);
+    assert_eq!(report.ratios, [0.25]);
+}
@@ -0,0 +1,91 @@
+/// A "type family", i.e. a type-level function that applies a type constructor, e.g. `Option<_>` or `&'a _`,
+/// to the types of the fields of a `<EnumName>VariantsFamily` struct generated by the `VariantsDataStruct`
+/// derive macro in the `family` mode.
+///
+/// The lifetime parameter of [`VariantFamily::Apply`] allows borrowing families, e.g. [`RefFamily`].
+///
+/// ```rust
+/// use variants_data_struct_runtime::VariantFamily;
+///
+/// /// The family of `Result<_, String>`.
+/// struct FallibleFamily;
+///
+/// impl VariantFamily for FallibleFamily {
+///     type Apply<'a, T: 'a> = Result<T, String>;
+/// }
+/// ```
+pub trait VariantFamily {
+    /// The type constructor applied to `T`.
+    type Apply<'a, T: 'a>;
+}
+
+/// A mapping between the values of two families, which is applied to each field of
+/// a `<EnumName>VariantsFamily` struct by its `map_family` method.
+pub trait FamilyMapper<F: VariantFamily, G: VariantFamily> {
+    /// Maps the value of the family `F` to the value of the family `G`.
+    fn map<'a, T: 'a>(&mut self, value: F::Apply<'a, T>) -> G::Apply<'a, T>;
+}
+
+/// The family of the types themselves, i.e. `T`.
+pub struct OwnedFamily;
+
+impl VariantFamily for OwnedFamily {
+    type Apply<'a, T: 'a> = T;
+}
+
+/// The family of the optional values, i.e. `Option<T>`.
+pub struct OptionFamily;
+
+impl VariantFamily for OptionFamily {
+    type Apply<'a, T: 'a> = Option<T>;
+}
+
+/// The family of the shared references, i.e. `&'a T`.
+pub struct RefFamily;
+
+impl VariantFamily for RefFamily {
+    type Apply<'a, T: 'a> = &'a T;
+}
+
+/// The family of the mutable references, i.e. `&'a mut T`.
+pub struct MutFamily;
+
+impl VariantFamily for MutFamily {
+    type Apply<'a, T: 'a> = &'a mut T;
+}
+
+/// The family of the boxed values, i.e. `Box<T>`.
+#[cfg(feature = "alloc")]
+pub struct BoxFamily;
+
+#[cfg(feature = "alloc")]
+impl VariantFamily for BoxFamily {
+    type Apply<'a, T: 'a> = alloc::boxed::Box<T>;
+}
+
+/// The family of the vectors, i.e. `Vec<T>`.
+#[cfg(feature = "alloc")]
+pub struct VecFamily;
+
+#[cfg(feature = "alloc")]
+impl VariantFamily for VecFamily {
+    type Apply<'a, T: 'a> = alloc::vec::Vec<T>;
+}
+
+/// The mapping of the owned values to the optional values, i.e. `Some`.
+pub struct IntoSome;
+
+impl FamilyMapper<OwnedFamily, OptionFamily> for IntoSome {
+    fn map<'a, T: 'a>(&mut self, value: T) -> Option<T> {
+        Some(value)
+    }
+}
+
+/// The mapping of the mutable references to the shared references.
+pub struct Reborrow;
+
+impl FamilyMapper<MutFamily, RefFamily> for Reborrow {
+    fn map<'a, T: 'a>(&mut self, value: &'a mut T) -> &'a T {
+        value
+    }
+}
@@ -0,0 +1,35 @@
+/// The zero-sized marker type of a variant, e.g. `my_enum_kinds::TupleEnum`, generated by the `VariantsDataStruct`
+/// derive macro in the `markers` mode.
+pub trait VariantMarker {
+    /// The name of the variant.
+    const NAME: &'static str;
+}
+
+/// The access to the field of a variant, identified by its marker type `K`, of a variants data struct
+/// generated by the `VariantsDataStruct` derive macro in the `markers` mode.
+///
+/// It allows generic code to access the fields of any derived variants data struct at the type level.
+///
+/// ```rust
+/// use variants_data_struct_runtime::{Field, VariantMarker};
+///
+/// /// Returns the name of the variant together with the data of its field.
+/// fn named_field<K: VariantMarker, D: Field<K>>(data: &D) -> (&'static str, &D::Output) {
+///     (K::NAME, data.get())
+/// }
+/// ```
+pub trait Field<K> {
+    /// The type of the field.
+    type Output;
+
+    /// Returns the reference to the field.
+    fn get(&self) -> &Self::Output;
+
+    /// Returns the mutable reference to the field.
+    fn get_mut(&mut self) -> &mut Self::Output;
+
+    /// Converts the data struct into the field.
+    fn into_field(self) -> Self::Output
+    where
+        Self: Sized;
+}
@@ -0,0 +1,17 @@
+#![doc = include_str!("../README.md")]
+#![no_std]
+
+#[cfg(feature = "alloc")]
+extern crate alloc;
+
+mod family;
+mod field;
+mod reflection;
+mod route;
+mod variants;
+
+pub use crate::family::*;
+pub use crate::field::*;
+pub use crate::reflection::*;
+pub use crate::route::*;
+pub use crate::variants::*;
@@ -0,0 +1,86 @@
+use core::fmt;
+use core::hash::{Hash, Hasher};
+
+/// The shape of a variant, i.e. whether it has no fields, unnamed fields or named fields.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum VariantShape {
+    /// A unit variant, e.g. `UnitEnum`.
+    Unit,
+    /// A tuple variant, e.g. `TupleEnum(i32, String)`.
+    Tuple,
+    /// A struct variant, e.g. `StructEnum { id: u32, name: String }`.
+    Struct,
+}
+
+/// The metadata of a field of a variant.
+///
+/// Two `FieldInfo`s are equal if their names and the names of their types are equal.
+#[derive(Clone, Copy)]
+pub struct FieldInfo {
+    /// The name of the field, or its index (e.g. `"0"`) for the fields of tuple variants.
+    pub name: &'static str,
+    /// The function returning the name of the type of the field (see [`FieldInfo::type_name`]).
+    pub type_name_fn: fn() -> &'static str,
+}
+
+impl FieldInfo {
+    /// Returns the name of the type of the field as given by [`core::any::type_name`], i.e. with the generic
+    /// parameters of the enum substituted, e.g. `"alloc::vec::Vec<u8>"` for `Vec<T>` of `MyEnum<u8>`.
+    pub fn type_name(&self) -> &'static str {
+        (self.type_name_fn)()
+    }
+}
+
+impl fmt::Debug for FieldInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FieldInfo")
+            .field("name", &self.name)
+            .field("type_name", &self.type_name())
+            .finish()
+    }
+}
+
+impl PartialEq for FieldInfo {
+    fn eq(&self, other: &Self) -> bool {
+        self.name == other.name && self.type_name() == other.type_name()
+    }
+}
+
+impl Eq for FieldInfo {}
+
+impl Hash for FieldInfo {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.name.hash(state);
+        self.type_name().hash(state);
+    }
+}
+
+/// The metadata of a variant, as listed in the `VARIANTS` constant generated by the `VariantsDataStruct`
+/// derive macro in the `reflection` mode.
+///
+/// ```rust
+/// use variants_data_struct_runtime::{VariantInfo, VariantShape};
+///
+/// /// Returns the names of the struct variants.
+/// fn struct_variants(variants: &[VariantInfo]) -> impl Iterator<Item = &'static str> + '_ {
+///     variants
+///         .iter()
+///         .filter(|variant| variant.shape == VariantShape::Struct)
+///         .map(|variant| variant.name)
+/// }
+/// ```
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct VariantInfo {
+    /// The name of the variant, e.g. `"TupleEnum"`.
+    pub name: &'static str,
+    /// The name of the field of the variant in the variants data struct, e.g. `"tuple_enum"`.
+    pub field_name: &'static str,
+    /// The name of the "variant type" of the variant, e.g. `"TupleEnumVariantType"`, if it is generated.
+    pub variant_ty_name: Option<&'static str>,
+    /// The shape of the variant.
+    pub shape: VariantShape,
+    /// The metadata of the fields of the variant.
+    pub fields: &'static [FieldInfo],
+    /// The discriminant of the variant.
+    pub discriminant: i128,
+}
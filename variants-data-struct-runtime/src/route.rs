@@ -0,0 +1,60 @@
+use core::fmt;
+
+/// The error of routing a value of the enum `E` to the channel of its variant with the `route` method
+/// of a `<EnumName>Router` generated by the `VariantsDataStruct` derive macro in the `router` mode,
+/// which occurs when the receiver of the variant is disconnected or the variant is skipped, i.e. has no channel.
+///
+/// The error holds the value that couldn't be routed.
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub struct RouteError<E> {
+    /// The value that couldn't be routed.
+    pub value: E,
+    /// The name of the variant of the value.
+    pub variant: &'static str,
+    /// Why the value couldn't be routed.
+    pub reason: RouteErrorReason,
+}
+
+/// The reason of a [`RouteError`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum RouteErrorReason {
+    /// The receiver of the variant is disconnected.
+    Disconnected,
+    /// The variant is skipped, so it has no channel.
+    Skipped,
+}
+
+impl<E> RouteError<E> {
+    /// Returns the value that couldn't be routed.
+    pub fn into_inner(self) -> E {
+        self.value
+    }
+}
+
+impl<E> fmt::Debug for RouteError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("RouteError")
+            .field("variant", &self.variant)
+            .field("reason", &self.reason)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<E> fmt::Display for RouteError<E> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self.reason {
+            RouteErrorReason::Disconnected => {
+                write!(f, "the receiver of `{}` is disconnected", self.variant)
+            }
+            RouteErrorReason::Skipped => {
+                write!(
+                    f,
+                    "the variant `{}` is skipped and has no receiver",
+                    self.variant
+                )
+            }
+        }
+    }
+}
+
+impl<E> core::error::Error for RouteError<E> {}
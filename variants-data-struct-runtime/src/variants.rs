@@ -0,0 +1,43 @@
+/// An enum deriving `VariantsDataStruct` in the `traits` mode, which links it to its generated types.
+///
+/// ```rust
+/// use variants_data_struct_runtime::HasVariantsData;
+///
+/// /// Returns the number of values of each kind of the enum.
+/// fn count_kinds<E: HasVariantsData>(values: &[E]) -> Vec<(E::Kind, usize)>
+/// where
+///     E::Kind: PartialEq,
+/// {
+///     let mut counts: Vec<(E::Kind, usize)> = Vec::with_capacity(E::VARIANT_COUNT);
+///     for value in values {
+///         let kind = value.kind();
+///         match counts.iter_mut().find(|(counted, _count)| *counted == kind) {
+///             Some((_counted, count)) => *count += 1,
+///             None => counts.push((kind, 1)),
+///         }
+///     }
+///     counts
+/// }
+/// ```
+pub trait HasVariantsData {
+    /// The generated variants data struct, i.e. `<EnumName>VariantsData`.
+    type Data;
+    /// The generated fieldless kind enum, i.e. `<EnumName>Kind`,
+    /// or `Option<<EnumName>Kind>` if some variants are skipped.
+    type Kind;
+    /// The number of the variants of the enum, except the skipped ones.
+    const VARIANT_COUNT: usize;
+
+    /// Returns the kind of the variant.
+    fn kind(&self) -> Self::Kind;
+}
+
+/// A "variant type" generated by the `VariantsDataStruct` derive macro in the `traits` mode
+/// for a variant of the enum `E`.
+pub trait VariantOf<E> {
+    /// The index of the variant in the enum, not counting the skipped variants.
+    const INDEX: usize;
+
+    /// Converts the variant type into the enum.
+    fn into_enum(self) -> E;
+}
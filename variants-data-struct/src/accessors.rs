@@ -0,0 +1,214 @@
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `accessors(...)` item of the `variants_data_struct` and `variants_data_struct_field` attributes.
+///
+/// For the resolved values (e.g., with defaults applied), see [`AccessorsMeta`].
+#[derive(Default)]
+pub(crate) struct AccessorsAttrMeta {
+    /// The override for the visibility of the `<field_name>(&self)` getters.
+    pub(crate) get: Option<syn::Visibility>,
+    /// The override for the visibility of the `<field_name>_mut(&mut self)` getters.
+    pub(crate) get_mut: Option<syn::Visibility>,
+    /// The override for the visibility of the `set_<field_name>(&mut self, value)` setters.
+    pub(crate) set: Option<syn::Visibility>,
+    /// The override for the visibility of the `with_<field_name>(self, value)` methods.
+    pub(crate) with: Option<syn::Visibility>,
+}
+
+impl syn::parse::Parse for AccessorsAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut get: Option<syn::Visibility> = None;
+        let mut get_mut: Option<syn::Visibility> = None;
+        let mut set: Option<syn::Visibility> = None;
+        let mut with: Option<syn::Visibility> = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "get" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let vis: syn::Visibility = input.parse()?;
+                    get = Some(vis);
+                }
+                "get_mut" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let vis: syn::Visibility = input.parse()?;
+                    get_mut = Some(vis);
+                }
+                "set" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let vis: syn::Visibility = input.parse()?;
+                    set = Some(vis);
+                }
+                "with" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let vis: syn::Visibility = input.parse()?;
+                    with = Some(vis);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in accessors of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(AccessorsAttrMeta {
+            get,
+            get_mut,
+            set,
+            with,
+        })
+    }
+}
+
+/// The resolved metadata for the accessors of a field of the derived variants data struct.
+///
+/// For the raw attribute meta, see [`AccessorsAttrMeta`].
+#[derive(Clone)]
+pub(crate) struct AccessorsMeta {
+    /// The visibility of the `<field_name>(&self)` getter.
+    pub(crate) get: syn::Visibility,
+    /// The visibility of the `<field_name>_mut(&mut self)` getter.
+    pub(crate) get_mut: syn::Visibility,
+    /// The visibility of the `set_<field_name>(&mut self, value)` setter.
+    pub(crate) set: syn::Visibility,
+    /// The visibility of the `with_<field_name>(self, value)` method.
+    pub(crate) with: syn::Visibility,
+}
+
+impl AccessorsMeta {
+    /// Resolves the accessors meta of the `variants_data_struct` attribute, where the visibilities
+    /// that aren't provided are that of the derived variants data struct.
+    pub(crate) fn resolve(attr_meta: AccessorsAttrMeta, vis: &syn::Visibility) -> AccessorsMeta {
+        let AccessorsAttrMeta {
+            get,
+            get_mut,
+            set,
+            with,
+        } = attr_meta;
+
+        AccessorsMeta {
+            get: get.unwrap_or_else(|| vis.clone()),
+            get_mut: get_mut.unwrap_or_else(|| vis.clone()),
+            set: set.unwrap_or_else(|| vis.clone()),
+            with: with.unwrap_or_else(|| vis.clone()),
+        }
+    }
+
+    /// Resolves the accessors meta of the `variants_data_struct_field` attribute, where the visibilities
+    /// that aren't provided are those of the given resolved accessors meta of the `variants_data_struct` attribute.
+    pub(crate) fn resolve_with_defaults(
+        attr_meta: AccessorsAttrMeta,
+        defaults: &AccessorsMeta,
+    ) -> AccessorsMeta {
+        let AccessorsAttrMeta {
+            get,
+            get_mut,
+            set,
+            with,
+        } = attr_meta;
+
+        AccessorsMeta {
+            get: get.unwrap_or_else(|| defaults.get.clone()),
+            get_mut: get_mut.unwrap_or_else(|| defaults.get_mut.clone()),
+            set: set.unwrap_or_else(|| defaults.set.clone()),
+            with: with.unwrap_or_else(|| defaults.with.clone()),
+        }
+    }
+}
+
+/// Generates the accessors of the fields of the derived variants data struct, i.e. the
+/// `<field_name>(&self)`, `<field_name>_mut(&mut self)`, `set_<field_name>(&mut self, value)` and
+/// `with_<field_name>(self, value)` methods for each field whose accessors are requested.
+pub(crate) fn accessors(
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let enum_ident = &original_enum.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let methods: Vec<proc_macro2::TokenStream> = original_enum
+        .variants
+        .iter()
+        .filter_map(|variant| {
+            let AccessorsMeta {
+                get,
+                get_mut,
+                set,
+                with,
+            } = variant.field_meta.accessors.as_ref()?;
+            let field_name = &variant.field_meta.field_name;
+            let field_ty = &variant.field_meta.field_ty;
+            let get_mut_fn = quote::format_ident!("{}_mut", field_name);
+            let set_fn = quote::format_ident!("set_{}", field_name);
+            let with_fn = quote::format_ident!("with_{}", field_name);
+            let get_doc = format!(
+                " Returns the reference to the data of [`{enum_ident}::{}`].",
+                variant.ident
+            );
+            let get_mut_doc = format!(
+                " Returns the mutable reference to the data of [`{enum_ident}::{}`].",
+                variant.ident
+            );
+            let set_doc = format!(
+                " Replaces the data of [`{enum_ident}::{}`], returning the previous data.",
+                variant.ident
+            );
+            let with_doc = format!(
+                " Returns the data struct with the data of [`{enum_ident}::{}`] replaced.",
+                variant.ident
+            );
+            Some(quote::quote! {
+                #[doc = #get_doc]
+                #get fn #field_name(&self) -> &#field_ty {
+                    &self.#field_name
+                }
+
+                #[doc = #get_mut_doc]
+                #get_mut fn #get_mut_fn(&mut self) -> &mut #field_ty {
+                    &mut self.#field_name
+                }
+
+                #[doc = #set_doc]
+                #set fn #set_fn(&mut self, value: #field_ty) -> #field_ty {
+                    ::core::mem::replace(&mut self.#field_name, value)
+                }
+
+                #[doc = #with_doc]
+                #[must_use]
+                #with fn #with_fn(mut self, value: #field_ty) -> Self {
+                    self.#field_name = value;
+                    self
+                }
+            })
+        })
+        .collect();
+
+    if methods.is_empty() {
+        return vec![];
+    }
+
+    vec![syn::parse_quote! {
+        impl #impl_generics #variants_data_struct_name #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }]
+}
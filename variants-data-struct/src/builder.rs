@@ -0,0 +1,671 @@
+use crate::generics::{UsedGenericParams, fresh_type_param, generic_args, phantom_marker_ident};
+use crate::missing_variants::missing_variants_name;
+use crate::original_enum::{OriginalEnum, OriginalVariant};
+use crate::variants_data_struct_defs::variants_data_struct_marker_ty;
+
+/// The meta of the `builder(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`BuilderMeta`].
+#[derive(Default)]
+pub(crate) struct BuilderAttrMeta {
+    /// The override for the name of the builder struct.
+    ///
+    /// If not provided, the default name is `<VariantsDataStructName>Builder`.
+    pub(crate) name: Option<syn::Ident>,
+    /// Whether the builder tracks the set fields in its type parameters, so that `build()`
+    /// is only available once every field is set, instead of returning the missing variants error.
+    pub(crate) typestate: bool,
+    /// The attributes to be applied to the builder struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for BuilderAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut typestate: bool = false;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "typestate" => {
+                    typestate = true;
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in builder of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(BuilderAttrMeta {
+            name,
+            typestate,
+            attrs,
+        })
+    }
+}
+
+/// The resolved metadata for the builder of the derived variants data struct.
+///
+/// For the raw attribute meta, see [`BuilderAttrMeta`].
+pub(crate) struct BuilderMeta {
+    /// The name of the builder struct.
+    pub(crate) name: syn::Ident,
+    /// Whether the builder tracks the set fields in its type parameters.
+    pub(crate) typestate: bool,
+    /// The attributes to be applied to the builder struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl BuilderMeta {
+    pub(crate) fn resolve(
+        attr_meta: BuilderAttrMeta,
+        variants_data_struct_name: &syn::Ident,
+    ) -> BuilderMeta {
+        let BuilderAttrMeta {
+            name,
+            typestate,
+            attrs,
+        } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => quote::format_ident!("{}Builder", variants_data_struct_name),
+        };
+
+        BuilderMeta {
+            name,
+            typestate,
+            attrs,
+        }
+    }
+}
+
+/// Generates the builder of the derived variants data struct, i.e.
+///
+/// * the `MyEnumVariantsDataBuilder` struct and the `builder()` function of the data struct,
+/// * the `<field_name>` setter for each field, which takes the fields of the variant as `impl Into<FieldType>`
+///   (see [`setter_param_ty`]) or a closure over the builder of the "variant type" of a struct variant,
+/// * the `set_<field_name>` setter for each field, which takes the value of the field,
+/// * the `build` method, which either returns the `MyEnumMissingVariants` error if some fields aren't set
+///   or, with `typestate`, is only available once every field is set,
+/// * the builder of each "variant type" of a struct variant (e.g. `StructEnumVariantTypeBuilder`)
+///   and its `builder()` function, whose type parameters track the set fields.
+pub(crate) fn builder(
+    // The resolved metadata for the builder.
+    builder_meta: &BuilderMeta,
+    // The visibility of the builder struct and its methods, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct, which are also the generics of the builder struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> syn::Result<Vec<syn::Item>> {
+    let BuilderMeta {
+        name,
+        typestate,
+        attrs,
+    } = builder_meta;
+
+    let enum_ident = &original_enum.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let data_args = generic_args(generics);
+    let missing_variants_name = missing_variants_name(enum_ident);
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let field_tys: Vec<&syn::Type> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_ty)
+        .collect();
+    let variant_names: Vec<String> = variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .collect();
+    let count = variants.len();
+
+    let marker_ident = phantom_marker_ident();
+    let data_marker_init = variants_data_struct_marker_ty(generics, variants)
+        .map(|_marker_ty| quote::quote!(#marker_ident: ::core::marker::PhantomData,));
+
+    let set_fns: Vec<syn::Ident> = field_names
+        .iter()
+        .map(|field_name| quote::format_ident!("set_{}", field_name))
+        .collect();
+
+    // The setters share the inherent impl with `build`, e.g. the setters of `SetTuple` and `Tuple` clash
+    let mut method_names: Vec<String> = vec!["build".to_string()];
+    for (variant, set_fn) in variants.iter().zip(&set_fns) {
+        for method_name in [&variant.field_meta.field_name, set_fn] {
+            let method_name = method_name.to_string();
+            if method_names.contains(&method_name) {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    format!(
+                        "conflicting `{method_name}` methods in the builder of variants_data_struct attribute"
+                    ),
+                ));
+            }
+            method_names.push(method_name);
+        }
+    }
+
+    let mut items: Vec<syn::Item> = vec![];
+    for variant in variants {
+        items.extend(variant_ty_builder(variant)?);
+    }
+
+    let setter_inputs: Vec<Option<(proc_macro2::TokenStream, syn::Expr)>> =
+        variants.iter().map(setter_input).collect();
+    let setter_docs: Vec<(String, String)> = variants
+        .iter()
+        .map(|variant| {
+            let fields_doc = match (&variant.fields, &variant.field_meta.variant_ty) {
+                (syn::Fields::Named(_), Some(variant_ty)) => format!(
+                    " Sets the data of [`{enum_ident}::{}`] with the builder of [`{}`].",
+                    variant.ident, variant_ty.name
+                ),
+                _ => format!(
+                    " Sets the data of [`{enum_ident}::{}`] from its fields.",
+                    variant.ident
+                ),
+            };
+            let value_doc = format!(" Sets the data of [`{enum_ident}::{}`].", variant.ident);
+            (fields_doc, value_doc)
+        })
+        .collect();
+    let doc = format!(" The builder of [`{variants_data_struct_name}`].");
+
+    if *typestate {
+        // The type parameters of the fields are named after the variants, e.g. `TupleEnum`,
+        // and are `()` until the respective field is set
+        let mut builder_generics = generics.clone();
+        let mut field_params: Vec<syn::Ident> = vec![];
+        for variant in variants {
+            let field_param = fresh_type_param(&builder_generics, &variant.ident.to_string());
+            builder_generics
+                .params
+                .push(syn::parse_quote!(#field_param));
+            field_params.push(field_param);
+        }
+        if !builder_generics.params.is_empty() {
+            builder_generics
+                .lt_token
+                .get_or_insert_with(Default::default);
+            builder_generics
+                .gt_token
+                .get_or_insert_with(Default::default);
+        }
+        let (builder_impl_generics, builder_ty_generics, builder_where_clause) =
+            builder_generics.split_for_impl();
+        let builder_params = &builder_generics.params;
+
+        // None of the generic parameters of the derived variants data struct is used by the fields of the builder
+        let (marker_field, marker_init) = match UsedGenericParams::new(generics).phantom_marker_ty()
+        {
+            Some(marker_ty) => (
+                Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+                Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+            ),
+            None => (None, None),
+        };
+
+        let fields = variants
+            .iter()
+            .zip(&field_params)
+            .map(|(variant, field_param)| {
+                let field_name = &variant.field_meta.field_name;
+                let doc = format!(" The data of [`{enum_ident}::{}`], if set.", variant.ident);
+                quote::quote! {
+                    #[doc = #doc]
+                    #vis #field_name: #field_param
+                }
+            });
+
+        let setters = variants.iter().enumerate().map(|(i, variant)| {
+            let field_name = &variant.field_meta.field_name;
+            let field_ty = &variant.field_meta.field_ty;
+            let set_fn = &set_fns[i];
+            let (fields_doc, value_doc) = &setter_docs[i];
+            let set_args = field_params.iter().enumerate().map(|(j, field_param)| {
+                if i == j {
+                    quote::quote!(#field_ty)
+                } else {
+                    quote::quote!(#field_param)
+                }
+            });
+            let set_ty: syn::Type = syn::parse_quote!(#name<#(#data_args,)* #(#set_args,)*>);
+            let other_field_names = field_names
+                .iter()
+                .enumerate()
+                .filter(|(j, _field_name)| i != *j)
+                .map(|(_j, field_name)| field_name)
+                .collect::<Vec<_>>();
+            let fields_setter = setter_inputs[i].as_ref().map(|(inputs, value)| {
+                quote::quote! {
+                    #[doc = #fields_doc]
+                    #vis fn #field_name(self, #inputs) -> #set_ty {
+                        #name {
+                            #field_name: #value,
+                            #(#other_field_names: self.#other_field_names,)*
+                            #marker_init
+                        }
+                    }
+                }
+            });
+            quote::quote! {
+                #fields_setter
+
+                #[doc = #value_doc]
+                #vis fn #set_fn(self, value: #field_ty) -> #set_ty {
+                    #name {
+                        #field_name: value,
+                        #(#other_field_names: self.#other_field_names,)*
+                        #marker_init
+                    }
+                }
+            }
+        });
+
+        let unset_fields = field_names.iter().map(|_field_name| quote::quote!(()));
+        let unset_ty: syn::Type = syn::parse_quote!(#name<#(#data_args,)* #(#unset_fields,)*>);
+        let complete_ty: syn::Type = syn::parse_quote!(#name<#(#data_args,)* #(#field_tys,)*>);
+
+        items.extend::<[syn::Item; 4]>([
+            syn::parse_quote! {
+                #[doc = #doc]
+                ///
+                /// The type parameters of the fields are `()` until the respective field is set.
+                #(#attrs)*
+                #vis struct #name<#builder_params> #builder_where_clause {
+                    #(#fields,)*
+                    #marker_field
+                }
+            },
+            syn::parse_quote! {
+                impl #impl_generics #variants_data_struct_name #ty_generics #where_clause {
+                    #[doc = concat!(" Returns the [`", stringify!(#name), "`] without any fields set.")]
+                    #vis fn builder() -> #unset_ty {
+                        #name {
+                            #(#field_names: (),)*
+                            #marker_init
+                        }
+                    }
+                }
+            },
+            syn::parse_quote! {
+                impl #builder_impl_generics #name #builder_ty_generics #builder_where_clause {
+                    #(#setters)*
+                }
+            },
+            syn::parse_quote! {
+                impl #impl_generics #complete_ty #where_clause {
+                    #[doc = concat!(" Returns the [`", stringify!(#variants_data_struct_name), "`] with the set fields.")]
+                    #vis fn build(self) -> #variants_data_struct_name #ty_generics {
+                        #variants_data_struct_name {
+                            #(#field_names: self.#field_names,)*
+                            #data_marker_init
+                        }
+                    }
+                }
+            },
+        ]);
+    } else {
+        let params = &generics.params;
+
+        // Mark the generic parameters of the derived variants data struct that no field uses
+        let mut used_generic_params = UsedGenericParams::new(generics);
+        field_tys
+            .iter()
+            .for_each(|field_ty| used_generic_params.visit_type(field_ty));
+        let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+            Some(marker_ty) => (
+                Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+                Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+            ),
+            None => (None, None),
+        };
+
+        let fields = variants.iter().map(|variant| {
+            let field_name = &variant.field_meta.field_name;
+            let field_ty = &variant.field_meta.field_ty;
+            let doc = format!(" The data of [`{enum_ident}::{}`], if set.", variant.ident);
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: ::core::option::Option<#field_ty>
+            }
+        });
+
+        let setters = variants.iter().enumerate().map(|(i, variant)| {
+            let field_name = &variant.field_meta.field_name;
+            let field_ty = &variant.field_meta.field_ty;
+            let set_fn = &set_fns[i];
+            let (fields_doc, value_doc) = &setter_docs[i];
+            let fields_setter = setter_inputs[i].as_ref().map(|(inputs, value)| {
+                quote::quote! {
+                    #[doc = #fields_doc]
+                    #vis fn #field_name(mut self, #inputs) -> Self {
+                        self.#field_name = ::core::option::Option::Some(#value);
+                        self
+                    }
+                }
+            });
+            quote::quote! {
+                #fields_setter
+
+                #[doc = #value_doc]
+                #vis fn #set_fn(mut self, value: #field_ty) -> Self {
+                    self.#field_name = ::core::option::Option::Some(value);
+                    self
+                }
+            }
+        });
+
+        items.extend::<[syn::Item; 4]>([
+            syn::parse_quote! {
+                #[doc = #doc]
+                #(#attrs)*
+                #vis struct #name<#params> #where_clause {
+                    #(#fields,)*
+                    #marker_field
+                }
+            },
+            syn::parse_quote! {
+                impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                    fn default() -> Self {
+                        Self {
+                            #(#field_names: ::core::option::Option::None,)*
+                            #marker_init
+                        }
+                    }
+                }
+            },
+            syn::parse_quote! {
+                impl #impl_generics #variants_data_struct_name #ty_generics #where_clause {
+                    #[doc = concat!(" Returns the [`", stringify!(#name), "`] without any fields set.")]
+                    #vis fn builder() -> #name #ty_generics {
+                        ::core::default::Default::default()
+                    }
+                }
+            },
+            syn::parse_quote! {
+                impl #impl_generics #name #ty_generics #where_clause {
+                    #(#setters)*
+
+                    #[doc = concat!(" Returns the [`", stringify!(#variants_data_struct_name), "`] if every field is set")]
+                    #[doc = concat!(" or the [`", stringify!(#missing_variants_name), "`] error otherwise.")]
+                    #vis fn build(
+                        self,
+                    ) -> ::core::result::Result<#variants_data_struct_name #ty_generics, #missing_variants_name> {
+                        let Self { #(#field_names,)* .. } = self;
+                        #[allow(unreachable_patterns)]
+                        match (#(#field_names,)*) {
+                            (#(::core::option::Option::Some(#field_names),)*) => {
+                                ::core::result::Result::Ok(#variants_data_struct_name {
+                                    #(#field_names,)*
+                                    #data_marker_init
+                                })
+                            }
+                            (#(#field_names,)*) => {
+                                let is_missing: [(bool, &'static str); #count] =
+                                    [#((#field_names.is_none(), #variant_names),)*];
+                                ::core::result::Result::Err(#missing_variants_name {
+                                    variants: is_missing
+                                        .into_iter()
+                                        .filter_map(|(is_missing, name)| is_missing.then_some(name))
+                                        .collect(),
+                                })
+                            }
+                        }
+                    }
+                }
+            },
+        ]);
+    }
+
+    Ok(items)
+}
+
+/// Returns the inputs of the `<field_name>` setter of the builder for the variant and the expression
+/// that constructs the value of the field from them, i.e.
+///
+/// * the closure over the builder of the "variant type", if the variant is a struct variant with one,
+/// * the fields of the variant (see [`OriginalVariant::bindings`]) otherwise,
+///
+/// or `None` if the value of the field can't be constructed (see [`OriginalVariant::field_value_expr`]).
+fn setter_input(variant: &OriginalVariant) -> Option<(proc_macro2::TokenStream, syn::Expr)> {
+    let field_value_expr = variant.field_value_expr()?;
+    match (&variant.fields, &variant.field_meta.variant_ty) {
+        (syn::Fields::Named(_), Some(variant_ty)) => {
+            let variant_ty_ty = variant_ty.ty();
+            let builder_name = variant_ty_builder_name(&variant_ty.name);
+            let variant_ty_args = generic_args(&variant_ty.generics);
+            let unset_fields = variant.fields.iter().map(|_field| quote::quote!(()));
+            let field_tys = variant.fields.iter().map(|field| &field.ty);
+            let payload_pattern = variant.payload_pattern();
+            Some((
+                quote::quote! {
+                    f: impl ::core::ops::FnOnce(
+                        #builder_name<#(#variant_ty_args,)* #(#unset_fields,)*>,
+                    ) -> #builder_name<#(#variant_ty_args,)* #(#field_tys,)*>
+                },
+                syn::parse_quote!({
+                    let #payload_pattern = f(<#variant_ty_ty>::builder()).build();
+                    #field_value_expr
+                }),
+            ))
+        }
+        _ => {
+            let bindings = variant.bindings();
+            let field_tys: Vec<&syn::Type> = variant.fields.iter().map(|field| &field.ty).collect();
+            let param_tys = field_tys.iter().map(|field_ty| setter_param_ty(field_ty));
+            let value_expr: syn::Expr = if bindings.is_empty() {
+                field_value_expr
+            } else {
+                syn::parse_quote!({
+                    #(let #bindings: #field_tys = ::core::convert::Into::into(#bindings);)*
+                    #field_value_expr
+                })
+            };
+            Some((quote::quote!(#(#bindings: #param_tys),*), value_expr))
+        }
+    }
+}
+
+/// Returns the type of the parameter of a setter for a field of the given type, i.e. `impl Into<FieldType>`,
+/// except for the primitive numeric types, whose literals (e.g. `1` for `u32`) couldn't be inferred otherwise.
+fn setter_param_ty(field_ty: &syn::Type) -> proc_macro2::TokenStream {
+    const NUMERIC_TYS: &[&str] = &[
+        "i8", "i16", "i32", "i64", "i128", "isize", "u8", "u16", "u32", "u64", "u128", "usize",
+        "f32", "f64",
+    ];
+
+    let is_numeric = match field_ty {
+        syn::Type::Path(syn::TypePath { qself: None, path }) => NUMERIC_TYS
+            .iter()
+            .any(|numeric_ty| path.is_ident(numeric_ty)),
+        _ => false,
+    };
+    if is_numeric {
+        quote::quote!(#field_ty)
+    } else {
+        quote::quote!(impl ::core::convert::Into<#field_ty>)
+    }
+}
+
+/// Returns the name of the builder of the "variant type", e.g. `StructEnumVariantTypeBuilder`.
+fn variant_ty_builder_name(variant_ty_name: &syn::Ident) -> syn::Ident {
+    quote::format_ident!("{}Builder", variant_ty_name)
+}
+
+/// Generates the builder of the "variant type" of the variant, if it is a struct variant with one, i.e.
+///
+/// * the `StructEnumVariantTypeBuilder` struct with a type parameter for each field,
+///   which is `()` until the field is set, and the `builder()` function of the "variant type",
+/// * a setter for each field and the `build` method, which is available once every field is set.
+fn variant_ty_builder(variant: &OriginalVariant) -> syn::Result<Vec<syn::Item>> {
+    use convert_case::Casing as _;
+
+    let (syn::Fields::Named(named_fields), Some(variant_ty)) =
+        (&variant.fields, &variant.field_meta.variant_ty)
+    else {
+        return Ok(vec![]);
+    };
+
+    // The setters share the inherent impl with `build`
+    if let Some(field) = named_fields
+        .named
+        .iter()
+        .find(|field| field.ident.as_ref().is_some_and(|ident| ident == "build"))
+    {
+        return Err(syn::Error::new_spanned(
+            field,
+            "conflicting `build` methods in the builder of variants_data_struct attribute",
+        ));
+    }
+
+    let vis = &variant_ty.vis;
+    let variant_ty_name = &variant_ty.name;
+    let variant_ty_ty = variant_ty.ty();
+    let name = variant_ty_builder_name(variant_ty_name);
+    let generics = &variant_ty.generics;
+    let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+    let variant_ty_args = generic_args(generics);
+
+    let bindings = variant.bindings();
+    let field_tys: Vec<&syn::Type> = variant.fields.iter().map(|field| &field.ty).collect();
+    let payload_expr = variant.payload_expr();
+
+    // The type parameters of the fields are named after the fields, e.g. `Id`
+    let mut builder_generics = generics.clone();
+    let mut field_params: Vec<syn::Ident> = vec![];
+    for binding in &bindings {
+        let param_name = binding
+            .to_string()
+            .from_case(convert_case::Case::Snake)
+            .to_case(convert_case::Case::Pascal);
+        let field_param = fresh_type_param(&builder_generics, &param_name);
+        builder_generics
+            .params
+            .push(syn::parse_quote!(#field_param));
+        field_params.push(field_param);
+    }
+    builder_generics
+        .lt_token
+        .get_or_insert_with(Default::default);
+    builder_generics
+        .gt_token
+        .get_or_insert_with(Default::default);
+    let (builder_impl_generics, builder_ty_generics, builder_where_clause) =
+        builder_generics.split_for_impl();
+    let builder_params = &builder_generics.params;
+
+    // None of the generic parameters of the "variant type" is used by the fields of the builder
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match UsedGenericParams::new(generics).phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+
+    let setters = bindings.iter().enumerate().map(|(i, binding)| {
+        let field_ty = field_tys[i];
+        let set_args = field_params.iter().enumerate().map(|(j, field_param)| {
+            if i == j {
+                quote::quote!(#field_ty)
+            } else {
+                quote::quote!(#field_param)
+            }
+        });
+        let other_bindings = bindings
+            .iter()
+            .enumerate()
+            .filter(|(j, _binding)| i != *j)
+            .map(|(_j, binding)| binding)
+            .collect::<Vec<_>>();
+        let param_ty = setter_param_ty(field_ty);
+        let doc = format!(" Sets the `{binding}` field.");
+        quote::quote! {
+            #[doc = #doc]
+            #vis fn #binding(self, #binding: #param_ty) -> #name<#(#variant_ty_args,)* #(#set_args,)*> {
+                #name {
+                    #binding: ::core::convert::Into::into(#binding),
+                    #(#other_bindings: self.#other_bindings,)*
+                    #marker_init
+                }
+            }
+        }
+    });
+
+    let unset_fields = bindings.iter().map(|_binding| quote::quote!(()));
+    let doc = format!(
+        " The builder of [`{variant_ty_name}`], whose type parameters are `()` until the respective field is set."
+    );
+
+    Ok(vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #vis struct #name<#builder_params> #builder_where_clause {
+                #(#vis #bindings: #field_params,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #variant_ty_ty #where_clause {
+                #[doc = concat!(" Returns the [`", stringify!(#name), "`] without any fields set.")]
+                #vis fn builder() -> #name<#(#variant_ty_args,)* #(#unset_fields,)*> {
+                    #name {
+                        #(#bindings: (),)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #builder_impl_generics #name #builder_ty_generics #builder_where_clause {
+                #(#setters)*
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #name<#(#variant_ty_args,)* #(#field_tys,)*> #where_clause {
+                #[doc = concat!(" Returns the [`", stringify!(#variant_ty_name), "`] with the set fields.")]
+                #vis fn build(self) -> #variant_ty_ty {
+                    let Self { #(#bindings,)* .. } = self;
+                    #payload_expr
+                }
+            }
+        },
+    ])
+}
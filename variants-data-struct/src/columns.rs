@@ -0,0 +1,363 @@
+use crate::generics::{
+    UsedGenericParams, fresh_lifetime, fresh_type_param, phantom_marker_ident, with_lifetime,
+};
+use crate::original_enum::OriginalEnum;
+use crate::views::{ViewKind, ViewsMeta, view_expr};
+
+/// The meta of the `columns(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`ColumnsMeta`].
+#[derive(Default)]
+pub(crate) struct ColumnsAttrMeta {
+    /// The override for the name of the columns struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Columns`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the columns struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for ColumnsAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in columns of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(ColumnsAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the columns struct of the original enum.
+///
+/// For the raw attribute meta, see [`ColumnsAttrMeta`].
+pub(crate) struct ColumnsMeta {
+    /// The name of the columns struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the columns struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl ColumnsMeta {
+    pub(crate) fn resolve(attr_meta: ColumnsAttrMeta, enum_ident: &syn::Ident) -> ColumnsMeta {
+        let ColumnsAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Columns"), enum_ident.span()),
+        };
+
+        ColumnsMeta { name, attrs }
+    }
+}
+
+/// Returns the smallest unsigned integer type that can hold the index of any of the variants.
+fn tag_ty(variants_count: usize) -> syn::Type {
+    if variants_count <= 1 << 8 {
+        syn::parse_quote!(u8)
+    } else if variants_count <= 1 << 16 {
+        syn::parse_quote!(u16)
+    } else {
+        syn::parse_quote!(u32)
+    }
+}
+
+/// Generates the columns struct of the original enum, i.e.
+///
+/// * the `MyEnumColumns` struct with a `Vec` of the values of the fields of the data struct for each variant
+///   and a vector of the variants' tags that keeps the order of the values,
+/// * its `Default`, `Extend<MyEnum>` and `FromIterator<MyEnum>` implementations,
+/// * its `push`, `len`, `is_empty`, `iter` and `into_enums` methods,
+/// * its methods returning the slices of the data of the respective variants, e.g. `tuple_enum()`.
+///
+/// Returns an error if the type of a field is overridden or the field is wrapped, since such values can't be
+/// converted back into the original enum, or if a field is named like a method or the tags of the columns struct.
+///
+/// ```rust,compile_fail
+/// use variants_data_struct::VariantsDataStruct;
+///
+/// // error: conflicting `push` names in the columns of variants_data_struct attribute
+/// #[derive(VariantsDataStruct)]
+/// #[variants_data_struct(columns)]
+/// pub enum StackOp {
+///     Push(u32),
+///     Pop,
+/// }
+/// ```
+pub(crate) fn columns(
+    // The resolved metadata for the columns struct.
+    columns_meta: &ColumnsMeta,
+    // The resolved metadata for the borrowed views, whose borrowed view enum the `iter` method yields.
+    views_meta: &ViewsMeta,
+    // The visibility of the columns struct, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The generics of the derived variants data struct, which are also the generics of the columns struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> syn::Result<Vec<syn::Item>> {
+    let ColumnsMeta { name, attrs } = columns_meta;
+    let ref_name = &views_meta.ref_name;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let field_tys: Vec<&syn::Type> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_ty)
+        .collect();
+    let mut field_value_patterns: Vec<syn::Pat> = vec![];
+    for variant in variants {
+        match variant.field_value_pattern() {
+            Some(field_value_pattern) => field_value_patterns.push(field_value_pattern),
+            None => {
+                return Err(syn::Error::new_spanned(
+                    &variant.ident,
+                    "columns can't store the fields with field_ty_override or field_wrapper of variants_data_struct attribute",
+                ));
+            }
+        }
+    }
+
+    // The columns share the inherent impl with the methods below and the struct with the tags,
+    // e.g. the columns of `Push` and `Tags` clash
+    let reserved_names = ["tags", "push", "len", "is_empty", "iter", "into_enums"];
+    for variant in variants {
+        let field_name = variant.field_meta.field_name.to_string();
+        if reserved_names.contains(&field_name.as_str()) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                format!(
+                    "conflicting `{field_name}` names in the columns of variants_data_struct attribute"
+                ),
+            ));
+        }
+    }
+    let count = variants.len();
+    let tag_ty = tag_ty(count);
+    let tags: Vec<syn::LitInt> = (0..count)
+        .map(|i| syn::LitInt::new(&i.to_string(), proc_macro2::Span::call_site()))
+        .collect();
+
+    // Mark the generic parameters of the columns struct that no field uses
+    let mut used_generic_params = UsedGenericParams::new(generics);
+    field_tys
+        .iter()
+        .for_each(|field_ty| used_generic_params.visit_type(field_ty));
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+
+    let lifetime = fresh_lifetime(generics, "a");
+    let ref_generics = with_lifetime(&original_enum.generics, &lifetime);
+    let (_ref_impl_generics, ref_ty_generics, _ref_where_clause) = ref_generics.split_for_impl();
+    let ref_ty: syn::Type = syn::parse_quote!(#ref_name #ref_ty_generics);
+
+    let push_arms = variants.iter().zip(&tags).map(|(variant, tag)| {
+        let field_name = &variant.field_meta.field_name;
+        let pattern = variant.pattern(enum_ident);
+        let field_value_expr = variant.field_value_expr();
+        quote::quote! {
+            #pattern => {
+                self.tags.push(#tag);
+                self.#field_name.push(#field_value_expr);
+            }
+        }
+    });
+
+    // The skipped variants have no column, so their values are returned back
+    let push_method = match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            /// Appends the value to the columns or returns it back if its variant is skipped.
+            #vis fn push(&mut self, value: #enum_ty) -> ::core::result::Result<(), #enum_ty> {
+                match value {
+                    #(#push_arms)*
+                    value @ (#skipped_pattern) => return ::core::result::Result::Err(value),
+                }
+                ::core::result::Result::Ok(())
+            }
+        },
+        None => quote::quote! {
+            /// Appends the value to the columns.
+            #vis fn push(&mut self, value: #enum_ty) {
+                match value {
+                    #(#push_arms)*
+                }
+            }
+        },
+    };
+
+    let iter_arms = variants.iter().zip(&field_value_patterns).zip(&tags).map(
+        |((variant, field_value_pattern), tag)| {
+            let field_name = &variant.field_meta.field_name;
+            let view_expr = view_expr(ViewKind::Ref, &lifetime, ref_name, variant);
+            quote::quote! {
+                #tag => {
+                    let #field_value_pattern = &self.#field_name[index];
+                    #view_expr
+                }
+            }
+        },
+    );
+
+    let into_enums_arms = variants.iter().zip(&field_value_patterns).zip(&tags).map(
+        |((variant, field_value_pattern), tag)| {
+            let field_name = &variant.field_meta.field_name;
+            let construct = variant.construct(enum_ident);
+            quote::quote! {
+                #tag => {
+                    let #field_value_pattern = #field_name.next().unwrap();
+                    #construct
+                }
+            }
+        },
+    );
+
+    let slice_methods = variants.iter().zip(&field_tys).map(|(variant, field_ty)| {
+        let field_name = &variant.field_meta.field_name;
+        let doc = format!(
+            " Returns the data of the [`{enum_ident}::{}`] values in the order they were pushed.",
+            variant.ident
+        );
+        quote::quote! {
+            #[doc = #doc]
+            #vis fn #field_name(&self) -> &[#field_ty] {
+                &self.#field_name
+            }
+        }
+    });
+
+    let iter_param = fresh_type_param(generics, "I");
+    let doc = format!(
+        " The values of [`{enum_ident}`] stored as a column of data for each variant, which keeps the order of the values."
+    );
+
+    Ok(vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #where_clause {
+                tags: ::std::vec::Vec<#tag_ty>,
+                #(#field_names: ::std::vec::Vec<#field_tys>,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        tags: ::std::vec::Vec::new(),
+                        #(#field_names: ::std::vec::Vec::new(),)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #push_method
+
+                /// Returns the number of values in the columns.
+                #vis fn len(&self) -> usize {
+                    self.tags.len()
+                }
+
+                /// Returns `true` if the columns contain no values.
+                #vis fn is_empty(&self) -> bool {
+                    self.tags.is_empty()
+                }
+
+                #[doc = concat!(" Returns an iterator over the borrowed views of the values, i.e. [`", stringify!(#ref_name), "`],")]
+                /// in the order they were pushed.
+                #vis fn iter<#lifetime>(&#lifetime self) -> impl ::core::iter::Iterator<Item = #ref_ty> + #lifetime {
+                    let mut indices: [usize; #count] = [0; #count];
+                    self.tags.iter().map(move |&tag| {
+                        let index = indices[tag as usize];
+                        indices[tag as usize] += 1;
+                        match tag {
+                            #(#iter_arms)*
+                            _ => ::core::unreachable!(),
+                        }
+                    })
+                }
+
+                /// Converts the columns back into the values in the order they were pushed.
+                #vis fn into_enums(self) -> ::std::vec::Vec<#enum_ty> {
+                    let Self { tags, #(#field_names,)* .. } = self;
+                    #(let mut #field_names = #field_names.into_iter();)*
+                    tags.into_iter()
+                        .map(|tag| match tag {
+                            #(#into_enums_arms)*
+                            _ => ::core::unreachable!(),
+                        })
+                        .collect()
+                }
+
+                #(#slice_methods)*
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::iter::Extend<#enum_ty> for #name #ty_generics #where_clause {
+                fn extend<#iter_param: ::core::iter::IntoIterator<Item = #enum_ty>>(&mut self, iter: #iter_param) {
+                    // The values of the skipped variants, if any, are dropped
+                    for value in iter {
+                        let _ = self.push(value);
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::iter::FromIterator<#enum_ty> for #name #ty_generics #where_clause {
+                fn from_iter<#iter_param: ::core::iter::IntoIterator<Item = #enum_ty>>(iter: #iter_param) -> Self {
+                    let mut columns = Self::default();
+                    columns.extend(iter);
+                    columns
+                }
+            }
+        },
+    ])
+}
@@ -0,0 +1,58 @@
+use crate::generics::merge_generics;
+use crate::original_enum::OriginalEnum;
+
+/// Generates the conversions between the original enum and the "variant types", i.e.
+///
+/// * `impl From<VariantType> for MyEnum` and
+/// * `impl TryFrom<MyEnum> for VariantType`, which returns the original enum as the error
+///   if it is a different variant,
+///
+/// for the variants that have "variant types" and don't opt out of the conversions.
+pub(crate) fn conversions(original_enum: &OriginalEnum) -> Vec<syn::ItemImpl> {
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+
+    original_enum
+        .variants
+        .iter()
+        .filter(|variant| variant.field_meta.conversions)
+        .filter_map(|variant| {
+            let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+            Some((variant, variant_ty))
+        })
+        .flat_map(|(variant, variant_ty)| {
+            let generics = merge_generics(original_enum.generics.clone(), variant_ty.generics.clone());
+            let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+            let variant_ty_ty = variant_ty.ty();
+            let pattern = variant.pattern(enum_ident);
+            let construct = variant.construct(enum_ident);
+            let payload_pattern = variant.payload_pattern();
+            let payload_expr = variant.payload_expr();
+
+            let from_variant_ty: syn::ItemImpl = syn::parse_quote! {
+                impl #impl_generics ::core::convert::From<#variant_ty_ty> for #enum_ty #where_clause {
+                    fn from(value: #variant_ty_ty) -> Self {
+                        let #payload_pattern = value;
+                        #construct
+                    }
+                }
+            };
+
+            let try_from_enum: syn::ItemImpl = syn::parse_quote! {
+                impl #impl_generics ::core::convert::TryFrom<#enum_ty> for #variant_ty_ty #where_clause {
+                    type Error = #enum_ty;
+
+                    fn try_from(value: #enum_ty) -> ::core::result::Result<Self, Self::Error> {
+                        #[allow(unreachable_patterns)]
+                        match value {
+                            #pattern => ::core::result::Result::Ok(#payload_expr),
+                            value => ::core::result::Result::Err(value),
+                        }
+                    }
+                }
+            };
+
+            [from_variant_ty, try_from_enum]
+        })
+        .collect()
+}
@@ -0,0 +1,180 @@
+use crate::original_enum::OriginalEnum;
+use crate::uniform::UniformMeta;
+
+/// The meta of the `counters(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`CountersMeta`].
+#[derive(Default)]
+pub(crate) struct CountersAttrMeta {
+    /// The override for the name of the counters struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Counters`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the counters struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for CountersAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in counters of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(CountersAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the counters struct of the original enum.
+///
+/// For the raw attribute meta, see [`CountersAttrMeta`].
+pub(crate) struct CountersMeta {
+    /// The name of the counters struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the counters struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl CountersMeta {
+    pub(crate) fn resolve(attr_meta: CountersAttrMeta, enum_ident: &syn::Ident) -> CountersMeta {
+        let CountersAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Counters"), enum_ident.span()),
+        };
+
+        CountersMeta { name, attrs }
+    }
+}
+
+/// Generates the counters struct of the original enum, i.e.
+///
+/// * the `MyEnumCounters` struct with an `AtomicU64` for each variant,
+/// * its `Default` implementation and `new` function,
+/// * its `record`, `snapshot` (returning the uniform `MyEnumMap<u64>`) and `reset` methods.
+pub(crate) fn counters(
+    // The resolved metadata for the counters struct.
+    counters_meta: &CountersMeta,
+    // The resolved metadata for the uniform map struct, which is the snapshot of the counters.
+    uniform_meta: &UniformMeta,
+    // The visibility of the counters struct and its fields, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let CountersMeta { name, attrs } = counters_meta;
+    let map_name = &uniform_meta.name;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let (enum_impl_generics, _enum_ty_generics, enum_where_clause) =
+        original_enum.generics.split_for_impl();
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let variant_idents: Vec<&syn::Ident> = variants.iter().map(|variant| &variant.ident).collect();
+
+    let fields = variants.iter().map(|variant| {
+        let field_name = &variant.field_meta.field_name;
+        let doc = format!(
+            " The number of recorded [`{enum_ident}::{}`].",
+            variant.ident
+        );
+        quote::quote! {
+            #[doc = #doc]
+            #vis #field_name: ::core::sync::atomic::AtomicU64
+        }
+    });
+
+    // The skipped variants have no counter, so their values aren't recorded
+    let skipped_arm = original_enum
+        .skipped_pattern()
+        .map(|skipped_pattern| quote::quote!(#skipped_pattern => return,));
+
+    let doc =
+        format!(" The atomic counters of the recorded values of each variant of [`{enum_ident}`].");
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name {
+                #(#fields,)*
+            }
+        },
+        syn::parse_quote! {
+            impl ::core::default::Default for #name {
+                fn default() -> Self {
+                    Self::new()
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #name {
+                /// Creates the counters with zero for every variant.
+                #vis const fn new() -> Self {
+                    Self {
+                        #(#field_names: ::core::sync::atomic::AtomicU64::new(0),)*
+                    }
+                }
+
+                /// Increments the counter of the variant of the value, unless the variant is skipped.
+                #vis fn record #enum_impl_generics(&self, value: &#enum_ty) #enum_where_clause {
+                    let counter = match *value {
+                        #(#enum_ident::#variant_idents { .. } => &self.#field_names,)*
+                        #skipped_arm
+                    };
+                    counter.fetch_add(1, ::core::sync::atomic::Ordering::Relaxed);
+                }
+
+                #[doc = concat!(" Returns the current value of each counter in the [`", stringify!(#map_name), "`].")]
+                #vis fn snapshot(&self) -> #map_name<u64> {
+                    #map_name {
+                        #(#field_names: self.#field_names.load(::core::sync::atomic::Ordering::Relaxed),)*
+                    }
+                }
+
+                /// Sets every counter to zero.
+                #vis fn reset(&self) {
+                    #(self.#field_names.store(0, ::core::sync::atomic::Ordering::Relaxed);)*
+                }
+            }
+        },
+    ]
+}
@@ -0,0 +1,112 @@
+use crate::generics::{UsedGenericParams, phantom_marker_ident};
+use crate::original_enum::OriginalEnum;
+use crate::variants_data_struct_defs::variants_data_struct_marker_ty;
+
+/// Returns the generics with the `Ty: Default` predicate added for each of the given types
+/// that mention the generic parameters.
+///
+/// The types that don't mention the generic parameters aren't bounded, since such bounds
+/// would be rejected rather than make the implementation conditional.
+fn default_generics<'t>(
+    generics: &syn::Generics,
+    tys: impl IntoIterator<Item = &'t syn::Type>,
+) -> syn::Generics {
+    let mut default_generics = generics.clone();
+    for ty in tys {
+        let mut used_generic_params = UsedGenericParams::new(generics);
+        used_generic_params.visit_type(ty);
+        if generics
+            .params
+            .iter()
+            .any(|param| used_generic_params.is_used(param))
+        {
+            default_generics
+                .make_where_clause()
+                .predicates
+                .push(syn::parse_quote!(#ty: ::core::default::Default));
+        }
+    }
+    default_generics
+}
+
+/// Generates the `Default` implementations of the derived variants data struct and the "variant types", i.e.
+///
+/// * `impl Default for MyEnumVariantsData`, which uses the `default = <expr>` of the `variants_data_struct_field`
+///   attribute of each variant, if specified, and `Default::default()` otherwise,
+/// * `impl Default for VariantType` for each "variant type", which uses the `default = <expr>` of the
+///   `variants_data_struct_field` attribute of each field of the variant, if specified, and `Default::default()` otherwise.
+pub(crate) fn defaults(
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let variants = &original_enum.variants;
+
+    let marker_ident = phantom_marker_ident();
+    let data_marker_init = variants_data_struct_marker_ty(generics, variants)
+        .map(|_marker_ty| quote::quote!(#marker_ident: ::core::marker::PhantomData,));
+
+    let field_names = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name);
+    let field_defaults = variants
+        .iter()
+        .map(|variant| match &variant.field_meta.default {
+            Some(default) => quote::quote!(#default),
+            None => quote::quote!(::core::default::Default::default()),
+        });
+    let defaulted_field_tys = variants
+        .iter()
+        .filter(|variant| variant.field_meta.default.is_none())
+        .map(|variant| &variant.field_meta.field_ty);
+    let data_generics = default_generics(generics, defaulted_field_tys);
+    let (_impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+    let (data_impl_generics, _data_ty_generics, data_where_clause) = data_generics.split_for_impl();
+
+    let mut items: Vec<syn::Item> = vec![syn::parse_quote! {
+        impl #data_impl_generics ::core::default::Default for #variants_data_struct_name #ty_generics #data_where_clause {
+            fn default() -> Self {
+                Self {
+                    #(#field_names: #field_defaults,)*
+                    #data_marker_init
+                }
+            }
+        }
+    }];
+
+    items.extend(variants.iter().filter_map(|variant| {
+        let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+        let variant_ty_ty = variant_ty.ty();
+        let bindings = variant.bindings();
+        let field_tys: Vec<&syn::Type> = variant.fields.iter().map(|field| &field.ty).collect();
+        let field_defaults =
+            variant
+                .field_defaults
+                .iter()
+                .map(|field_default| match field_default {
+                    Some(default) => quote::quote!(#default),
+                    None => quote::quote!(::core::default::Default::default()),
+                });
+        let defaulted_field_tys = field_tys
+            .iter()
+            .zip(&variant.field_defaults)
+            .filter(|(_field_ty, field_default)| field_default.is_none())
+            .map(|(field_ty, _field_default)| *field_ty);
+        let generics = default_generics(&variant_ty.generics, defaulted_field_tys);
+        let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+        let payload_expr = variant.payload_expr();
+        Some(syn::parse_quote! {
+            impl #impl_generics ::core::default::Default for #variant_ty_ty #where_clause {
+                fn default() -> Self {
+                    #(let #bindings: #field_tys = #field_defaults;)*
+                    #payload_expr
+                }
+            }
+        })
+    }));
+
+    items
+}
@@ -0,0 +1,149 @@
+use crate::generics::method_generics;
+use crate::original_enum::{OriginalEnum, OriginalVariant};
+
+/// Returns the type of the references to the fields of the variant, i.e.
+///
+/// * `()` for variants without fields,
+/// * `&T` (or `&mut T`) for variants with a single field,
+/// * the tuple of the references to the fields otherwise.
+fn fields_refs_ty(variant: &OriginalVariant, mutability: Option<syn::Token![mut]>) -> syn::Type {
+    let refs: Vec<syn::Type> = variant
+        .fields
+        .iter()
+        .map(|field| {
+            let ty = &field.ty;
+            syn::parse_quote!(&#mutability #ty)
+        })
+        .collect();
+    match refs.as_slice() {
+        [ty] => ty.clone(),
+        _ => syn::parse_quote!((#(#refs,)*)),
+    }
+}
+
+/// Returns the expression that combines the bound fields of the variant (see [`OriginalVariant::bindings`])
+/// in the same shape as [`fields_refs_ty`].
+fn fields_refs_expr(variant: &OriginalVariant) -> syn::Expr {
+    let bindings = variant.bindings();
+    match bindings.as_slice() {
+        [binding] => syn::parse_quote!(#binding),
+        _ => syn::parse_quote!((#(#bindings,)*)),
+    }
+}
+
+/// Generates the methods of the original enum for each of its variants, e.g. for `MyEnum::TupleEnum`:
+///
+/// * `is_tuple_enum(&self) -> bool`,
+/// * `as_tuple_enum(&self) -> Option<(&i32, &String)>`,
+/// * `as_tuple_enum_mut(&mut self) -> Option<(&mut i32, &mut String)>`,
+/// * `into_tuple_enum(self) -> Result<TupleEnumVariantType, Self>`,
+/// * `map_tuple_enum(self, f: impl FnOnce(TupleEnumVariantType) -> TupleEnumVariantType) -> Self`.
+///
+/// The names of the methods are derived from the names of the fields in the data struct.
+pub(crate) fn enum_methods(
+    // The visibility of the methods, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> syn::ItemImpl {
+    let enum_ident = &original_enum.ident;
+    let enum_generics = &original_enum.generics;
+    let (impl_generics, ty_generics, where_clause) = enum_generics.split_for_impl();
+
+    let methods = original_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let field_name = &variant.field_meta.field_name;
+        let is_fn = quote::format_ident!("is_{}", field_name);
+        let as_fn = quote::format_ident!("as_{}", field_name);
+        let as_mut_fn = quote::format_ident!("as_{}_mut", field_name);
+        let into_fn = quote::format_ident!("into_{}", field_name);
+        let map_fn = quote::format_ident!("map_{}", field_name);
+
+        let pattern = variant.pattern(enum_ident);
+        let construct = variant.construct(enum_ident);
+        let payload_ty = variant.payload_ty();
+        let payload_pattern = variant.payload_pattern();
+        let payload_expr = variant.payload_expr();
+        let refs_ty = fields_refs_ty(variant, None);
+        let refs_mut_ty = fields_refs_ty(variant, Some(Default::default()));
+        let refs_expr = fields_refs_expr(variant);
+
+        // The "variant type" may have generic parameters that the original enum doesn't declare
+        let payload_generics = match &variant.field_meta.variant_ty {
+            Some(variant_ty) => method_generics(enum_generics, variant_ty.generics.clone()),
+            None => syn::Generics::default(),
+        };
+        let (_payload_impl_generics, _payload_ty_generics, payload_where_clause) =
+            payload_generics.split_for_impl();
+        let payload_params = &payload_generics.params;
+
+        let is_doc = format!(" Returns `true` if the enum is [`{enum_ident}::{ident}`].");
+        let as_doc = format!(
+            " Returns the references to the fields of [`{enum_ident}::{ident}`], if the enum is this variant."
+        );
+        let as_mut_doc = format!(
+            " Returns the mutable references to the fields of [`{enum_ident}::{ident}`], if the enum is this variant."
+        );
+        let into_doc = format!(
+            " Returns the data of [`{enum_ident}::{ident}`], if the enum is this variant, or the enum itself otherwise."
+        );
+        let map_doc = format!(
+            " Maps the data of [`{enum_ident}::{ident}`] with the given function, if the enum is this variant."
+        );
+
+        quote::quote! {
+            #[doc = #is_doc]
+            #vis fn #is_fn(&self) -> bool {
+                ::core::matches!(self, #enum_ident::#ident { .. })
+            }
+
+            #[doc = #as_doc]
+            #vis fn #as_fn(&self) -> ::core::option::Option<#refs_ty> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #pattern => ::core::option::Option::Some(#refs_expr),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = #as_mut_doc]
+            #vis fn #as_mut_fn(&mut self) -> ::core::option::Option<#refs_mut_ty> {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #pattern => ::core::option::Option::Some(#refs_expr),
+                    _ => ::core::option::Option::None,
+                }
+            }
+
+            #[doc = #into_doc]
+            #vis fn #into_fn<#payload_params>(self) -> ::core::result::Result<#payload_ty, Self> #payload_where_clause {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #pattern => ::core::result::Result::Ok(#payload_expr),
+                    value => ::core::result::Result::Err(value),
+                }
+            }
+
+            #[doc = #map_doc]
+            #vis fn #map_fn<#payload_params>(
+                self,
+                f: impl ::core::ops::FnOnce(#payload_ty) -> #payload_ty,
+            ) -> Self #payload_where_clause {
+                // The bindings of the fields may shadow `f`, so the payload is moved out of the match first
+                #[allow(unreachable_patterns)]
+                let payload: #payload_ty = match self {
+                    #pattern => #payload_expr,
+                    value => return value,
+                };
+                let #payload_pattern = f(payload);
+                #construct
+            }
+        }
+    });
+
+    syn::parse_quote! {
+        impl #impl_generics #enum_ident #ty_generics #where_clause {
+            #(#methods)*
+        }
+    }
+}
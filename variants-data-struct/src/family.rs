@@ -0,0 +1,308 @@
+use crate::generics::{UsedGenericParams, fresh_lifetime, fresh_type_param, phantom_marker_ident};
+use crate::original_enum::OriginalEnum;
+use crate::variants_data_struct_defs::variants_data_struct_marker_ty;
+
+/// The meta of the `family(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`FamilyMeta`].
+#[derive(Default)]
+pub(crate) struct FamilyAttrMeta {
+    /// The override for the name of the family struct.
+    ///
+    /// If not provided, the default name is `<EnumName>VariantsFamily`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the family struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for FamilyAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in family of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(FamilyAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the family struct of the original enum.
+///
+/// For the raw attribute meta, see [`FamilyAttrMeta`].
+pub(crate) struct FamilyMeta {
+    /// The name of the family struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the family struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl FamilyMeta {
+    pub(crate) fn resolve(attr_meta: FamilyAttrMeta, enum_ident: &syn::Ident) -> FamilyMeta {
+        let FamilyAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}VariantsFamily"), enum_ident.span()),
+        };
+
+        FamilyMeta { name, attrs }
+    }
+}
+
+/// Returns the generics of the family struct, i.e. the generics of the derived variants data struct
+/// with the lifetime of the family (e.g. `'f`) prepended and the family parameter (e.g. `F: VariantFamily`)
+/// inserted after the lifetimes, together with the predicates that the other parameters outlive the lifetime of the family.
+fn family_generics(
+    generics: &syn::Generics,
+    lifetime: &syn::Lifetime,
+    family_param: &syn::Ident,
+    runtime: &syn::Path,
+) -> syn::Generics {
+    let mut family_generics = generics.clone();
+    let lifetimes_count = family_generics.lifetimes().count();
+    family_generics.params.insert(
+        lifetimes_count,
+        syn::parse_quote!(#family_param: #runtime::VariantFamily),
+    );
+    family_generics
+        .params
+        .insert(0, syn::parse_quote!(#lifetime));
+    family_generics
+        .lt_token
+        .get_or_insert_with(Default::default);
+    family_generics
+        .gt_token
+        .get_or_insert_with(Default::default);
+
+    // The fields of the family struct are `F::Apply<'f, T>`, which requires `T: 'f`
+    let outlives_predicates: Vec<syn::WherePredicate> = generics
+        .params
+        .iter()
+        .filter_map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime_param) => {
+                let param_lifetime = &lifetime_param.lifetime;
+                Some(syn::parse_quote!(#param_lifetime: #lifetime))
+            }
+            syn::GenericParam::Type(type_param) => {
+                let ident = &type_param.ident;
+                Some(syn::parse_quote!(#ident: #lifetime))
+            }
+            syn::GenericParam::Const(_) => None,
+        })
+        .collect();
+    family_generics
+        .make_where_clause()
+        .predicates
+        .extend(outlives_predicates);
+
+    family_generics
+}
+
+/// Returns the type of the family struct with the given family, e.g. `MyEnumVariantsFamily<'f, OwnedFamily, T>`.
+fn family_ty(
+    name: &syn::Ident,
+    generics: &syn::Generics,
+    lifetime: &syn::Lifetime,
+    family: &syn::Type,
+) -> syn::Type {
+    let lifetime_args = generics
+        .lifetimes()
+        .map(|lifetime_param| &lifetime_param.lifetime);
+    let other_args = generics.params.iter().filter_map(|param| match param {
+        syn::GenericParam::Lifetime(_) => None,
+        syn::GenericParam::Type(type_param) => Some(&type_param.ident),
+        syn::GenericParam::Const(const_param) => Some(&const_param.ident),
+    });
+    syn::parse_quote!(#name<#lifetime, #(#lifetime_args,)* #family, #(#other_args,)*>)
+}
+
+/// Generates the family struct of the original enum, i.e.
+///
+/// * the `MyEnumVariantsFamily<'f, F>` struct with a field of type `F::Apply<'f, FieldType>` for each field
+///   of the derived variants data struct,
+/// * its `map_family` method, which maps each field with a [`FamilyMapper`],
+/// * the conversions between `MyEnumVariantsFamily<'f, OwnedFamily>` and the derived variants data struct.
+///
+/// [`FamilyMapper`]: https://docs.rs/variants-data-struct-runtime/latest/variants_data_struct_runtime/trait.FamilyMapper.html
+pub(crate) fn family(
+    // The resolved metadata for the family struct.
+    family_meta: &FamilyMeta,
+    // The visibility of the family struct and its fields, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+    // The path to the `variants-data-struct-runtime` crate.
+    runtime: &syn::Path,
+) -> Vec<syn::Item> {
+    let FamilyMeta { name, attrs } = family_meta;
+
+    let enum_ident = &original_enum.ident;
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let field_tys: Vec<&syn::Type> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_ty)
+        .collect();
+
+    let lifetime = fresh_lifetime(generics, "f");
+    let family_param = fresh_type_param(generics, "F");
+    let other_family_param = fresh_type_param(generics, "G");
+    let family_generics = family_generics(generics, &lifetime, &family_param, runtime);
+    let (family_impl_generics, family_ty_generics, family_where_clause) =
+        family_generics.split_for_impl();
+    let family_params = &family_generics.params;
+
+    // The conversions from and to the derived variants data struct use the owned family
+    let mut owned_generics = generics.clone();
+    owned_generics
+        .params
+        .insert(0, syn::parse_quote!(#lifetime));
+    owned_generics.lt_token.get_or_insert_with(Default::default);
+    owned_generics.gt_token.get_or_insert_with(Default::default);
+    owned_generics.where_clause = family_generics.where_clause.clone();
+    let (owned_impl_generics, _owned_ty_generics, owned_where_clause) =
+        owned_generics.split_for_impl();
+    let owned_family_ty = family_ty(
+        name,
+        generics,
+        &lifetime,
+        &syn::parse_quote!(#runtime::OwnedFamily),
+    );
+    let other_family_ty = family_ty(
+        name,
+        generics,
+        &lifetime,
+        &syn::parse_quote!(#other_family_param),
+    );
+    let (_impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+    let variants_data_struct_ty: syn::Type =
+        syn::parse_quote!(#variants_data_struct_name #ty_generics);
+
+    let family_field_tys: Vec<syn::Type> = field_tys
+        .iter()
+        .map(|field_ty| {
+            syn::parse_quote!(
+                <#family_param as #runtime::VariantFamily>::Apply<#lifetime, #field_ty>
+            )
+        })
+        .collect();
+
+    // Mark the generic parameters of the family struct that no field uses
+    let mut used_generic_params = UsedGenericParams::new(&family_generics);
+    family_field_tys
+        .iter()
+        .for_each(|family_field_ty| used_generic_params.visit_type(family_field_ty));
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+    let data_marker_init = variants_data_struct_marker_ty(generics, variants)
+        .map(|_marker_ty| quote::quote!(#marker_ident: ::core::marker::PhantomData,));
+
+    let fields = variants
+        .iter()
+        .zip(&family_field_tys)
+        .map(|(variant, family_field_ty)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(
+                " The data of [`{enum_ident}::{}`] in the family.",
+                variant.ident
+            );
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: #family_field_ty
+            }
+        });
+
+    let doc = format!(
+        " The data of each variant of [`{enum_ident}`] in the family `F`, e.g. `Option<_>` or `&'f _`."
+    );
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#family_params> #family_where_clause {
+                #(#fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #family_impl_generics #name #family_ty_generics #family_where_clause {
+                /// Maps the data of each variant to the family `G` with the mapper.
+                #vis fn map_family<#other_family_param: #runtime::VariantFamily>(
+                    self,
+                    mut mapper: impl #runtime::FamilyMapper<#family_param, #other_family_param>,
+                ) -> #other_family_ty {
+                    #name {
+                        #(#field_names: mapper.map::<#field_tys>(self.#field_names),)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #owned_impl_generics ::core::convert::From<#variants_data_struct_ty> for #owned_family_ty #owned_where_clause {
+                fn from(data: #variants_data_struct_ty) -> Self {
+                    #name {
+                        #(#field_names: data.#field_names,)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #owned_impl_generics ::core::convert::From<#owned_family_ty> for #variants_data_struct_ty #owned_where_clause {
+                fn from(family: #owned_family_ty) -> Self {
+                    #variants_data_struct_name {
+                        #(#field_names: family.#field_names,)*
+                        #data_marker_init
+                    }
+                }
+            }
+        },
+    ]
+}
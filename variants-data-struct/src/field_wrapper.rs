@@ -0,0 +1,84 @@
+use syn::visit::Visit;
+use syn::visit_mut::VisitMut;
+
+/// Finds the placeholders (i.e. `_`) in a field wrapper template, e.g. `Option<_>`.
+struct Placeholders {
+    count: usize,
+}
+
+impl<'ast> Visit<'ast> for Placeholders {
+    fn visit_type_infer(&mut self, _type_infer: &'ast syn::TypeInfer) {
+        self.count += 1;
+    }
+}
+
+/// Replaces the placeholders (i.e. `_`) in a field wrapper template with the wrapped type.
+struct ReplacePlaceholders<'a> {
+    ty: &'a syn::Type,
+}
+
+impl VisitMut for ReplacePlaceholders<'_> {
+    fn visit_type_mut(&mut self, ty: &mut syn::Type) {
+        if let syn::Type::Infer(_) = ty {
+            *ty = self.ty.clone();
+            return;
+        }
+        syn::visit_mut::visit_type_mut(self, ty);
+    }
+}
+
+/// Parses a field wrapper template, e.g. `Option<_>`, which must contain the `_` placeholder.
+///
+/// The bare `_` is accepted as well and means that the field isn't wrapped.
+pub(crate) fn parse_field_wrapper(input: syn::parse::ParseStream) -> syn::Result<syn::Type> {
+    let template: syn::Type = input.parse()?;
+    let mut placeholders = Placeholders { count: 0 };
+    placeholders.visit_type(&template);
+    if placeholders.count == 0 {
+        return Err(syn::Error::new_spanned(
+            template,
+            "field_wrapper must contain the `_` placeholder for the field type, e.g. `Option<_>`",
+        ));
+    }
+    Ok(template)
+}
+
+/// Returns the field wrapper template, unless it is the bare `_`, which disables the wrapping.
+pub(crate) fn enabled_field_wrapper(template: syn::Type) -> Option<syn::Type> {
+    match template {
+        syn::Type::Infer(_) => None,
+        template => Some(template),
+    }
+}
+
+/// Returns the type of the field wrapped with the template, e.g. `Option<TupleEnumVariantType>` for `Option<_>`.
+pub(crate) fn wrap_field_ty(template: &syn::Type, ty: &syn::Type) -> syn::Type {
+    let mut wrapped_ty = template.clone();
+    ReplacePlaceholders { ty }.visit_type_mut(&mut wrapped_ty);
+    wrapped_ty
+}
+
+/// Returns the function that wraps the values of the fields with the template, if the template is a standard
+/// wrapper that implements `From<T>`, e.g. `Option<_>`, `Box<_>` or `Arc<_>`.
+///
+/// Other templates, e.g. `Vec<_>`, require the `field_wrap` item to construct the wrapped values.
+pub(crate) fn default_field_wrap(template: &syn::Type) -> Option<syn::Expr> {
+    const FROM_WRAPPERS: &[&str] = &[
+        "Option", "Box", "Rc", "Arc", "Cell", "RefCell", "Mutex", "RwLock",
+    ];
+
+    let syn::Type::Path(syn::TypePath { qself: None, path }) = template else {
+        return None;
+    };
+    let segment = path.segments.last()?;
+    let syn::PathArguments::AngleBracketed(args) = &segment.arguments else {
+        return None;
+    };
+    let is_from_wrapper = FROM_WRAPPERS.iter().any(|wrapper| segment.ident == wrapper)
+        && args.args.len() == 1
+        && matches!(
+            args.args.first(),
+            Some(syn::GenericArgument::Type(syn::Type::Infer(_)))
+        );
+    is_from_wrapper.then(|| syn::parse_quote!(::core::convert::Into::into))
+}
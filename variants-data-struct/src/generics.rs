@@ -0,0 +1,436 @@
+use std::collections::HashSet;
+
+use syn::visit::Visit;
+
+/// The set of declared generic parameters (usually, those of the original enum)
+/// that are referenced by some syntax tree nodes.
+///
+/// Elided lifetimes (e.g. in `fn(&str) -> &str` or `dyn Fn(&str)`), `'_`, `'static`, and the lifetimes
+/// introduced by `for<...>` binders are not declared generic parameters and are ignored.
+pub(crate) struct UsedGenericParams<'g> {
+    /// The declared generics.
+    declared_generics: &'g syn::Generics,
+    /// The identifiers of the used lifetime parameters (without the leading apostrophe).
+    lifetimes: HashSet<syn::Ident>,
+    /// The identifiers of the used type and const parameters.
+    params: HashSet<syn::Ident>,
+}
+
+impl<'g> UsedGenericParams<'g> {
+    pub(crate) fn new(declared_generics: &'g syn::Generics) -> Self {
+        Self {
+            declared_generics,
+            lifetimes: HashSet::new(),
+            params: HashSet::new(),
+        }
+    }
+
+    /// Records the declared generic parameters referenced by the given type.
+    pub(crate) fn visit_type(&mut self, ty: &syn::Type) {
+        Visit::visit_type(self, ty);
+    }
+
+    fn is_declared_param(&self, ident: &syn::Ident) -> bool {
+        self.declared_generics
+            .params
+            .iter()
+            .any(|param| match param {
+                syn::GenericParam::Type(type_param) => type_param.ident == *ident,
+                syn::GenericParam::Const(const_param) => const_param.ident == *ident,
+                syn::GenericParam::Lifetime(_) => false,
+            })
+    }
+
+    fn is_declared_lifetime(&self, lifetime: &syn::Lifetime) -> bool {
+        self.declared_generics
+            .lifetimes()
+            .any(|lifetime_param| lifetime_param.lifetime.ident == lifetime.ident)
+    }
+
+    pub(crate) fn is_used(&self, param: &syn::GenericParam) -> bool {
+        match param {
+            syn::GenericParam::Type(type_param) => self.params.contains(&type_param.ident),
+            syn::GenericParam::Const(const_param) => self.params.contains(&const_param.ident),
+            syn::GenericParam::Lifetime(lifetime_param) => {
+                self.lifetimes.contains(&lifetime_param.lifetime.ident)
+            }
+        }
+    }
+
+    fn is_empty(&self) -> bool {
+        self.lifetimes.is_empty() && self.params.is_empty()
+    }
+
+    fn is_subset(&self, other: &Self) -> bool {
+        self.lifetimes.is_subset(&other.lifetimes) && self.params.is_subset(&other.params)
+    }
+
+    /// Returns the declared generic parameters referenced by the nodes visited by `visit`.
+    fn mentioned(&self, visit: impl FnOnce(&mut UsedGenericParams<'g>)) -> UsedGenericParams<'g> {
+        let mut mentioned = UsedGenericParams::new(self.declared_generics);
+        visit(&mut mentioned);
+        mentioned
+    }
+
+    /// Returns `true` if the nodes visited by `visit` reference some declared generic parameters
+    /// and all of them are used.
+    fn covers(&self, visit: impl FnOnce(&mut UsedGenericParams<'g>)) -> bool {
+        let mentioned = self.mentioned(visit);
+        !mentioned.is_empty() && mentioned.is_subset(self)
+    }
+
+    /// Returns `true` if the nodes visited by `visit` reference only used declared generic parameters, if any.
+    fn allows(&self, visit: impl FnOnce(&mut UsedGenericParams<'g>)) -> bool {
+        self.mentioned(visit).is_subset(self)
+    }
+
+    /// Returns the subset of the declared generics that are used,
+    /// together with the bounds and where-clause predicates that only involve used parameters.
+    pub(crate) fn to_generics(&self) -> syn::Generics {
+        let params =
+            self.declared_generics
+                .params
+                .iter()
+                .filter(|param| self.is_used(param))
+                .cloned()
+                .map(|mut param| {
+                    match &mut param {
+                        syn::GenericParam::Type(type_param) => {
+                            type_param.bounds = std::mem::take(&mut type_param.bounds)
+                                .into_iter()
+                                .filter(|bound| {
+                                    self.allows(|used| used.visit_type_param_bound(bound))
+                                })
+                                .collect();
+                            if type_param.default.as_ref().is_some_and(|default| {
+                                !self.allows(|used| used.visit_type(default))
+                            }) {
+                                type_param.eq_token = None;
+                                type_param.default = None;
+                            }
+                        }
+                        syn::GenericParam::Const(const_param) => {
+                            if const_param.default.as_ref().is_some_and(|default| {
+                                !self.allows(|used| used.visit_expr(default))
+                            }) {
+                                const_param.eq_token = None;
+                                const_param.default = None;
+                            }
+                        }
+                        syn::GenericParam::Lifetime(lifetime_param) => {
+                            lifetime_param.bounds = std::mem::take(&mut lifetime_param.bounds)
+                                .into_iter()
+                                .filter(|bound| self.allows(|used| used.visit_lifetime(bound)))
+                                .collect();
+                        }
+                    }
+                    param
+                })
+                .collect::<syn::punctuated::Punctuated<_, _>>();
+
+        let where_clause = self
+            .declared_generics
+            .where_clause
+            .as_ref()
+            .map(|where_clause| syn::WhereClause {
+                where_token: where_clause.where_token,
+                predicates: where_clause
+                    .predicates
+                    .iter()
+                    .filter(|predicate| self.covers(|used| used.visit_where_predicate(predicate)))
+                    .cloned()
+                    .collect(),
+            })
+            .filter(|where_clause| !where_clause.predicates.is_empty());
+
+        syn::Generics {
+            lt_token: (!params.is_empty())
+                .then(|| self.declared_generics.lt_token.unwrap_or_default()),
+            gt_token: (!params.is_empty())
+                .then(|| self.declared_generics.gt_token.unwrap_or_default()),
+            params,
+            where_clause,
+        }
+    }
+
+    /// Returns the type of a [`PhantomData`](core::marker::PhantomData) marker field that uses
+    /// all the declared lifetime and type parameters that are not used, if there are any.
+    ///
+    /// Const parameters don't need to be used.
+    pub(crate) fn phantom_marker_ty(&self) -> Option<syn::Type> {
+        let unused_tys: Vec<syn::Type> = self
+            .declared_generics
+            .params
+            .iter()
+            .filter(|param| !self.is_used(param))
+            .filter_map(|param| match param {
+                syn::GenericParam::Lifetime(lifetime_param) => {
+                    let lifetime = &lifetime_param.lifetime;
+                    Some(syn::parse_quote!(&#lifetime ()))
+                }
+                syn::GenericParam::Type(type_param) => {
+                    let ident = &type_param.ident;
+                    Some(syn::parse_quote!(fn() -> #ident))
+                }
+                syn::GenericParam::Const(_) => None,
+            })
+            .collect();
+
+        if unused_tys.is_empty() {
+            return None;
+        }
+
+        Some(syn::parse_quote!(::core::marker::PhantomData<(#(#unused_tys,)*)>))
+    }
+}
+
+/// The name of the [`PhantomData`](core::marker::PhantomData) marker field of the generated structs,
+/// which is added when some of their lifetime or type parameters are not used by any other field.
+pub(crate) fn phantom_marker_ident() -> syn::Ident {
+    syn::Ident::new("_marker", proc_macro2::Span::call_site())
+}
+
+/// Returns the member of the [`PhantomData`](core::marker::PhantomData) marker field that is added
+/// to a struct with the given fields, i.e. the next index for tuple structs and `_marker` otherwise.
+pub(crate) fn phantom_marker_member(fields: &syn::Fields) -> syn::Member {
+    match fields {
+        syn::Fields::Unnamed(unnamed_fields) => syn::Member::Unnamed(syn::Index {
+            index: unnamed_fields.unnamed.len() as u32,
+            span: proc_macro2::Span::call_site(),
+        }),
+        syn::Fields::Named(_) | syn::Fields::Unit => syn::Member::Named(phantom_marker_ident()),
+    }
+}
+
+/// Adds the [`PhantomData`](core::marker::PhantomData) marker field with the given type and visibility
+/// to the given fields (see [`phantom_marker_member`]).
+pub(crate) fn push_phantom_marker(
+    fields: &mut syn::Fields,
+    vis: &syn::Visibility,
+    marker_ty: syn::Type,
+) {
+    let marker_ident = phantom_marker_ident();
+    match fields {
+        syn::Fields::Unit => {
+            *fields = syn::Fields::Named(syn::parse_quote!({
+                #[doc(hidden)]
+                #vis #marker_ident: #marker_ty
+            }));
+        }
+        syn::Fields::Named(named_fields) => {
+            named_fields.named.push(syn::parse_quote!(
+                #[doc(hidden)]
+                #vis #marker_ident: #marker_ty
+            ));
+        }
+        syn::Fields::Unnamed(unnamed_fields) => {
+            unnamed_fields.unnamed.push(syn::Field {
+                attrs: vec![syn::parse_quote!(#[doc(hidden)])],
+                vis: vis.clone(),
+                mutability: syn::FieldMutability::None,
+                ident: None,
+                colon_token: None,
+                ty: marker_ty,
+            });
+        }
+    }
+}
+
+/// Returns a lifetime named after `name` (e.g. `'a`) that is not declared by the given generics,
+/// appending a number to the name if necessary (e.g. `'a1`).
+pub(crate) fn fresh_lifetime(generics: &syn::Generics, name: &str) -> syn::Lifetime {
+    let is_declared = |ident: &str| {
+        generics
+            .lifetimes()
+            .any(|lifetime_param| lifetime_param.lifetime.ident == ident)
+    };
+    let ident = std::iter::once(name.to_string())
+        .chain((1..).map(|i| format!("{name}{i}")))
+        .find(|ident| !is_declared(ident))
+        .unwrap();
+    syn::Lifetime::new(&format!("'{ident}"), proc_macro2::Span::call_site())
+}
+
+/// Returns a type parameter named after `name` (e.g. `I`) that is not declared by the given generics,
+/// appending a number to the name if necessary (e.g. `I1`).
+pub(crate) fn fresh_type_param(generics: &syn::Generics, name: &str) -> syn::Ident {
+    let is_declared = |ident: &str| {
+        generics.params.iter().any(|param| match param {
+            syn::GenericParam::Lifetime(_) => false,
+            param => generic_param_ident(param) == ident,
+        })
+    };
+    let ident = std::iter::once(name.to_string())
+        .chain((1..).map(|i| format!("{name}{i}")))
+        .find(|ident| !is_declared(ident))
+        .unwrap();
+    syn::Ident::new(&ident, proc_macro2::Span::call_site())
+}
+
+/// Returns the given generics with the given lifetime parameter prepended.
+pub(crate) fn with_lifetime(generics: &syn::Generics, lifetime: &syn::Lifetime) -> syn::Generics {
+    let mut generics = generics.clone();
+    generics.params.insert(
+        0,
+        syn::GenericParam::Lifetime(syn::LifetimeParam::new(lifetime.clone())),
+    );
+    generics.lt_token.get_or_insert_with(Default::default);
+    generics.gt_token.get_or_insert_with(Default::default);
+    generics
+}
+
+/// Returns the generic arguments for the given generics, e.g. `'a, T, N` for `<'a, T: Clone, const N: usize>`.
+pub(crate) fn generic_args(generics: &syn::Generics) -> Vec<proc_macro2::TokenStream> {
+    generics
+        .params
+        .iter()
+        .map(|param| match param {
+            syn::GenericParam::Lifetime(lifetime_param) => {
+                let lifetime = &lifetime_param.lifetime;
+                quote::quote!(#lifetime)
+            }
+            syn::GenericParam::Type(type_param) => {
+                let ident = &type_param.ident;
+                quote::quote!(#ident)
+            }
+            syn::GenericParam::Const(const_param) => {
+                let ident = &const_param.ident;
+                quote::quote!(#ident)
+            }
+        })
+        .collect()
+}
+
+fn generic_param_ident(param: &syn::GenericParam) -> &syn::Ident {
+    match param {
+        syn::GenericParam::Lifetime(lifetime_param) => &lifetime_param.lifetime.ident,
+        syn::GenericParam::Type(type_param) => &type_param.ident,
+        syn::GenericParam::Const(const_param) => &const_param.ident,
+    }
+}
+
+/// Merges the additional generic parameters and where-clause predicates into the given generics.
+///
+/// The additional lifetime parameters are placed after the existing ones and the additional
+/// type and const parameters are placed at the end. If an additional parameter is already declared,
+/// it is not declared again and its bounds are moved to the where clause instead.
+pub(crate) fn merge_generics(
+    mut generics: syn::Generics,
+    additional: syn::Generics,
+) -> syn::Generics {
+    let syn::Generics {
+        params: additional_params,
+        where_clause: additional_where_clause,
+        ..
+    } = additional;
+
+    for param in additional_params {
+        let is_declared = generics
+            .params
+            .iter()
+            .any(|declared| generic_param_ident(declared) == generic_param_ident(&param));
+
+        if is_declared {
+            let predicate: Option<syn::WherePredicate> = match param {
+                syn::GenericParam::Lifetime(lifetime_param)
+                    if !lifetime_param.bounds.is_empty() =>
+                {
+                    let syn::LifetimeParam {
+                        lifetime, bounds, ..
+                    } = lifetime_param;
+                    Some(syn::parse_quote!(#lifetime: #bounds))
+                }
+                syn::GenericParam::Type(type_param) if !type_param.bounds.is_empty() => {
+                    let syn::TypeParam { ident, bounds, .. } = type_param;
+                    Some(syn::parse_quote!(#ident: #bounds))
+                }
+                _ => None,
+            };
+            generics.make_where_clause().predicates.extend(predicate);
+            continue;
+        }
+
+        match param {
+            syn::GenericParam::Lifetime(_) => {
+                let lifetimes_count = generics.lifetimes().count();
+                generics.params.insert(lifetimes_count, param);
+            }
+            _ => generics.params.push(param),
+        }
+    }
+
+    if let Some(additional_where_clause) = additional_where_clause {
+        generics
+            .make_where_clause()
+            .predicates
+            .extend(additional_where_clause.predicates);
+    }
+
+    if !generics.params.is_empty() {
+        generics.lt_token.get_or_insert_with(Default::default);
+        generics.gt_token.get_or_insert_with(Default::default);
+    }
+
+    generics
+}
+
+/// Returns the generics that a method in an `impl` block with the given generics has to declare
+/// to additionally use the given generics (see [`merge_generics`]), i.e. the generic parameters
+/// that the `impl` block doesn't declare and the additional where-clause predicates.
+pub(crate) fn method_generics(
+    impl_generics: &syn::Generics,
+    additional: syn::Generics,
+) -> syn::Generics {
+    let impl_predicates_count = impl_generics
+        .where_clause
+        .as_ref()
+        .map_or(0, |where_clause| where_clause.predicates.len());
+    let merged = merge_generics(impl_generics.clone(), additional);
+
+    let mut generics = syn::Generics {
+        params: merged
+            .params
+            .into_iter()
+            .filter(|param| {
+                !impl_generics
+                    .params
+                    .iter()
+                    .any(|declared| generic_param_ident(declared) == generic_param_ident(param))
+            })
+            .collect(),
+        ..Default::default()
+    };
+    let predicates: Vec<syn::WherePredicate> = merged
+        .where_clause
+        .into_iter()
+        .flat_map(|where_clause| where_clause.predicates)
+        .skip(impl_predicates_count)
+        .collect();
+    if !predicates.is_empty() {
+        generics.make_where_clause().predicates.extend(predicates);
+    }
+    if !generics.params.is_empty() {
+        generics.lt_token = Some(Default::default());
+        generics.gt_token = Some(Default::default());
+    }
+
+    generics
+}
+
+impl<'ast> Visit<'ast> for UsedGenericParams<'_> {
+    fn visit_path(&mut self, path: &'ast syn::Path) {
+        if path.leading_colon.is_none()
+            && let Some(first_segment) = path.segments.first()
+            && self.is_declared_param(&first_segment.ident)
+        {
+            self.params.insert(first_segment.ident.clone());
+        }
+        syn::visit::visit_path(self, path);
+    }
+
+    fn visit_lifetime(&mut self, lifetime: &'ast syn::Lifetime) {
+        if self.is_declared_lifetime(lifetime) {
+            self.lifetimes.insert(lifetime.ident.clone());
+        }
+    }
+}
@@ -0,0 +1,352 @@
+use crate::generics::{UsedGenericParams, fresh_lifetime, fresh_type_param, phantom_marker_ident};
+use crate::missing_variants::missing_variants_name;
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `handlers(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`HandlersMeta`].
+#[derive(Default)]
+pub(crate) struct HandlersAttrMeta {
+    /// The override for the name of the handlers struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Handlers`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the handlers struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for HandlersAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in handlers of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(HandlersAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the handlers struct of the original enum.
+///
+/// For the raw attribute meta, see [`HandlersAttrMeta`].
+pub(crate) struct HandlersMeta {
+    /// The name of the handlers struct.
+    pub(crate) name: syn::Ident,
+    /// The name of the builder of the handlers struct, i.e. `<HandlersName>Builder`.
+    pub(crate) builder_name: syn::Ident,
+    /// The attributes to be applied to the handlers struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl HandlersMeta {
+    pub(crate) fn resolve(attr_meta: HandlersAttrMeta, enum_ident: &syn::Ident) -> HandlersMeta {
+        let HandlersAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Handlers"), enum_ident.span()),
+        };
+
+        let builder_name = quote::format_ident!("{}Builder", name);
+
+        HandlersMeta {
+            name,
+            builder_name,
+            attrs,
+        }
+    }
+}
+
+/// Generates the handlers struct of the original enum, i.e.
+///
+/// * the `MyEnumHandlers<'a, R>` struct with a boxed `FnMut(Payload) -> R + 'a` closure for each variant, which
+///   takes the "payload" of the variant (see [`OriginalVariant::payload_pattern`](crate::original_enum::OriginalVariant::payload_pattern)),
+/// * its `new`, `builder` and `handle` methods and its `Default` implementation (for `R: Default`),
+/// * the `MyEnumHandlersBuilder<'a, R>` struct with a setter for each handler and the `build`,
+///   `build_or_default` and `build_or_panic` methods.
+pub(crate) fn handlers(
+    // The resolved metadata for the handlers struct.
+    handlers_meta: &HandlersMeta,
+    // The visibility of the handlers struct and its builder, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let HandlersMeta {
+        name,
+        builder_name,
+        attrs,
+    } = handlers_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let enum_generics = &original_enum.generics;
+    let missing_variants_name = missing_variants_name(enum_ident);
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let variant_names: Vec<String> = variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .collect();
+    let payload_tys: Vec<syn::Type> = variants
+        .iter()
+        .map(|variant| variant.payload_ty())
+        .collect();
+    let count = variants.len();
+
+    // The handlers struct has the lifetime of the closures (e.g. `'a`) and their return type (e.g. `R`)
+    // in addition to the generics of the original enum
+    let lifetime = fresh_lifetime(enum_generics, "a");
+    let return_param = fresh_type_param(enum_generics, "R");
+    let mut handlers_generics = enum_generics.clone();
+    let lifetimes_count = handlers_generics.lifetimes().count();
+    handlers_generics
+        .params
+        .insert(lifetimes_count, syn::parse_quote!(#return_param));
+    handlers_generics
+        .params
+        .insert(0, syn::parse_quote!(#lifetime));
+    handlers_generics
+        .lt_token
+        .get_or_insert_with(Default::default);
+    handlers_generics
+        .gt_token
+        .get_or_insert_with(Default::default);
+    let (impl_generics, ty_generics, where_clause) = handlers_generics.split_for_impl();
+    let params = &handlers_generics.params;
+
+    let mut default_generics = handlers_generics.clone();
+    default_generics
+        .make_where_clause()
+        .predicates
+        .push(syn::parse_quote!(#return_param: ::core::default::Default));
+    let (_default_impl_generics, _default_ty_generics, default_where_clause) =
+        default_generics.split_for_impl();
+
+    let handler_tys: Vec<syn::Type> = payload_tys
+        .iter()
+        .map(|payload_ty| {
+            syn::parse_quote!(
+                ::std::boxed::Box<dyn ::core::ops::FnMut(#payload_ty) -> #return_param + #lifetime>
+            )
+        })
+        .collect();
+
+    // Mark the generic parameters of the original enum that no payload uses
+    let mut used_generic_params = UsedGenericParams::new(&handlers_generics);
+    handler_tys
+        .iter()
+        .for_each(|handler_ty| used_generic_params.visit_type(handler_ty));
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+
+    let fields = variants
+        .iter()
+        .zip(&handler_tys)
+        .map(|(variant, handler_ty)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(" The handler of [`{enum_ident}::{}`].", variant.ident);
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: #handler_ty
+            }
+        });
+
+    let handle_arms = variants.iter().map(|variant| {
+        let field_name = &variant.field_meta.field_name;
+        let pattern = variant.pattern(enum_ident);
+        let payload_expr = variant.payload_expr();
+        quote::quote! {
+            #pattern => (self.#field_name)(#payload_expr),
+        }
+    });
+
+    // The skipped variants have no handler, so their values are returned back
+    let handle_method = match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            /// Calls the handler of the variant of the value with its data
+            /// or returns the value back if its variant is skipped.
+            #vis fn handle(&mut self, value: #enum_ty) -> ::core::result::Result<#return_param, #enum_ty> {
+                ::core::result::Result::Ok(match value {
+                    #(#handle_arms)*
+                    value @ (#skipped_pattern) => return ::core::result::Result::Err(value),
+                })
+            }
+        },
+        None => quote::quote! {
+            /// Calls the handler of the variant of the value with its data.
+            #vis fn handle(&mut self, value: #enum_ty) -> #return_param {
+                match value {
+                    #(#handle_arms)*
+                }
+            }
+        },
+    };
+
+    let setters = variants.iter().zip(&payload_tys).map(|(variant, payload_ty)| {
+        let field_name = &variant.field_meta.field_name;
+        let doc = format!(" Sets the handler of [`{enum_ident}::{}`].", variant.ident);
+        quote::quote! {
+            #[doc = #doc]
+            #vis fn #field_name(
+                mut self,
+                handler: impl ::core::ops::FnMut(#payload_ty) -> #return_param + #lifetime,
+            ) -> Self {
+                self.#field_name = ::core::option::Option::Some(::std::boxed::Box::new(handler));
+                self
+            }
+        }
+    });
+
+    let panicking_handlers = variants.iter().zip(&payload_tys).map(|(variant, payload_ty)| {
+        let message = format!("no handler for `{enum_ident}::{}`", variant.ident);
+        quote::quote! {
+            ::std::boxed::Box::new(|_: #payload_ty| -> #return_param { ::core::panic!(#message) })
+        }
+    });
+
+    let doc = format!(" The handler closures of the variants of [`{enum_ident}`].");
+    let builder_doc = format!(" The builder of [`{name}`].");
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #where_clause {
+                #(#fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                /// Creates the handlers from the handler of each variant.
+                #[allow(clippy::too_many_arguments)]
+                #vis fn new(
+                    #(#field_names: impl ::core::ops::FnMut(#payload_tys) -> #return_param + #lifetime,)*
+                ) -> Self {
+                    Self {
+                        #(#field_names: ::std::boxed::Box::new(#field_names),)*
+                        #marker_init
+                    }
+                }
+
+                #[doc = concat!(" Returns the [`", stringify!(#builder_name), "`] without any handlers.")]
+                #vis fn builder() -> #builder_name #ty_generics {
+                    #builder_name {
+                        #(#field_names: ::core::option::Option::None,)*
+                        #marker_init
+                    }
+                }
+
+                #handle_method
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #default_where_clause {
+                /// Returns the handlers that return `R::default()` for every variant.
+                fn default() -> Self {
+                    Self::builder().build_or_default()
+                }
+            }
+        },
+        syn::parse_quote! {
+            #[doc = #builder_doc]
+            #vis struct #builder_name<#params> #where_clause {
+                #(#field_names: ::core::option::Option<#handler_tys>,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #builder_name #ty_generics #where_clause {
+                #(#setters)*
+
+                #[doc = concat!(" Returns the [`", stringify!(#name), "`] if the handler of every variant is set")]
+                #[doc = concat!(" or the [`", stringify!(#missing_variants_name), "`] error otherwise.")]
+                #vis fn build(self) -> ::core::result::Result<#name #ty_generics, #missing_variants_name> {
+                    let Self { #(#field_names,)* .. } = self;
+                    match (#(#field_names,)*) {
+                        (#(::core::option::Option::Some(#field_names),)*) => {
+                            ::core::result::Result::Ok(#name {
+                                #(#field_names,)*
+                                #marker_init
+                            })
+                        }
+                        (#(#field_names,)*) => {
+                            let is_missing: [(bool, &'static str); #count] =
+                                [#((#field_names.is_none(), #variant_names),)*];
+                            ::core::result::Result::Err(#missing_variants_name {
+                                variants: is_missing
+                                    .into_iter()
+                                    .filter_map(|(is_missing, name)| is_missing.then_some(name))
+                                    .collect(),
+                            })
+                        }
+                    }
+                }
+
+                #[doc = concat!(" Returns the [`", stringify!(#name), "`] whose missing handlers return `R::default()`.")]
+                #vis fn build_or_default(self) -> #name #ty_generics
+                where
+                    #return_param: ::core::default::Default,
+                {
+                    #name {
+                        #(#field_names: self.#field_names.unwrap_or_else(|| {
+                            ::std::boxed::Box::new(|_| ::core::default::Default::default())
+                        }),)*
+                        #marker_init
+                    }
+                }
+
+                #[doc = concat!(" Returns the [`", stringify!(#name), "`] whose missing handlers panic when called.")]
+                #vis fn build_or_panic(self) -> #name #ty_generics {
+                    #name {
+                        #(#field_names: self.#field_names.unwrap_or_else(|| #panicking_handlers),)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+    ]
+}
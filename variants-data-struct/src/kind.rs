@@ -0,0 +1,315 @@
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `kind(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`KindMeta`].
+#[derive(Default)]
+pub(crate) struct KindAttrMeta {
+    /// The override for the name of the kind enum.
+    ///
+    /// If not provided, the default name is `<EnumName>Kind`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The override for the visibility of the kind enum.
+    ///
+    /// If not provided, the visibility of the derived variants data struct is used.
+    pub(crate) vis: Option<syn::Visibility>,
+    /// The additional derives for the kind enum.
+    pub(crate) derives: Vec<syn::Path>,
+    /// The attributes to be applied to the kind enum.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for KindAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut vis: Option<syn::Visibility> = None;
+        let mut derives: Vec<syn::Path> = vec![];
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "vis" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let vis_value: syn::Visibility = input.parse()?;
+                    vis = Some(vis_value);
+                }
+                "derives" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let paths =
+                        content.parse_terminated(syn::Path::parse_mod_style, syn::Token![,])?;
+                    derives.extend(paths);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in kind of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(KindAttrMeta {
+            name,
+            vis,
+            derives,
+            attrs,
+        })
+    }
+}
+
+/// The resolved metadata for the kind enum of the original enum.
+///
+/// For the raw attribute meta, see [`KindAttrMeta`].
+pub(crate) struct KindMeta {
+    /// The name of the kind enum.
+    pub(crate) name: syn::Ident,
+    /// The visibility of the kind enum, its methods and the `kind` method of the original enum.
+    pub(crate) vis: syn::Visibility,
+    /// The additional derives for the kind enum.
+    pub(crate) derives: Vec<syn::Path>,
+    /// The attributes to be applied to the kind enum.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl KindMeta {
+    pub(crate) fn resolve(
+        attr_meta: KindAttrMeta,
+        enum_ident: &syn::Ident,
+        variants_data_struct_vis: &syn::Visibility,
+    ) -> KindMeta {
+        let KindAttrMeta {
+            name,
+            vis,
+            derives,
+            attrs,
+        } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Kind"), enum_ident.span()),
+        };
+
+        let vis = match vis {
+            Some(vis) => vis,
+            None => variants_data_struct_vis.clone(),
+        };
+
+        KindMeta {
+            name,
+            vis,
+            derives,
+            attrs,
+        }
+    }
+}
+
+/// Generates the fieldless kind enum of the original enum, i.e.
+///
+/// * the `MyEnumKind` enum with the same variants (and explicit discriminants) as the original enum, except the skipped ones,
+/// * its `ALL` and `COUNT` constants and the `name` method,
+/// * its `discriminant` and `from_discriminant` methods, if the original enum has explicit discriminants,
+/// * its `FromStr` implementation with the `ParseMyEnumKindError` error,
+/// * the `kind` method of the original enum.
+pub(crate) fn kind(
+    // The resolved metadata for the kind enum.
+    kind_meta: &KindMeta,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let KindMeta {
+        name,
+        vis,
+        derives,
+        attrs,
+    } = kind_meta;
+
+    let enum_ident = &original_enum.ident;
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) =
+        original_enum.generics.split_for_impl();
+    let parse_error_name = quote::format_ident!("Parse{}Error", name);
+
+    let variant_idents: Vec<&syn::Ident> = original_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.ident)
+        .collect();
+    let variant_names: Vec<String> = variant_idents
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect();
+    let count = variant_idents.len();
+
+    let kind_variants = original_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let doc = format!(" The kind of [`{enum_ident}::{ident}`].");
+        let discriminant = variant
+            .discriminant
+            .as_ref()
+            .map(|discriminant| quote::quote!(= #discriminant));
+        quote::quote! {
+            #[doc = #doc]
+            #ident #discriminant
+        }
+    });
+
+    let repr = original_enum
+        .repr
+        .as_ref()
+        .map(|repr| quote::quote!(#[repr(#repr)]));
+
+    let doc = format!(" The kind of [`{enum_ident}`], i.e. its variant without the data.");
+
+    let mut items: Vec<syn::Item> = vec![];
+
+    items.push(syn::parse_quote! {
+        #[doc = #doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+            ::core::hash::Hash,
+            ::core::cmp::PartialOrd,
+            ::core::cmp::Ord,
+            #(#derives,)*
+        )]
+        #(#attrs)*
+        #repr
+        #vis enum #name {
+            #(#kind_variants,)*
+        }
+    });
+
+    let discriminant_methods = original_enum.explicit_discriminants.then(|| {
+        let repr: syn::Ident = match &original_enum.repr {
+            Some(repr) => repr.clone(),
+            None => syn::Ident::new("isize", proc_macro2::Span::call_site()),
+        };
+        quote::quote! {
+            /// Returns the discriminant of the kind, which is the same as that of the respective variant.
+            #vis const fn discriminant(self) -> #repr {
+                self as #repr
+            }
+
+            /// Returns the kind with the given discriminant, if there is one.
+            #vis fn from_discriminant(discriminant: #repr) -> ::core::option::Option<Self> {
+                Self::ALL
+                    .into_iter()
+                    .find(|kind| kind.discriminant() == discriminant)
+            }
+        }
+    });
+
+    items.push(syn::parse_quote! {
+        impl #name {
+            /// All kinds in the order of the variants.
+            #vis const ALL: [Self; #count] = [#(Self::#variant_idents,)*];
+
+            /// The number of kinds.
+            #vis const COUNT: usize = #count;
+
+            /// Returns the name of the respective variant.
+            #vis const fn name(self) -> &'static str {
+                match self {
+                    #(Self::#variant_idents => #variant_names,)*
+                }
+            }
+
+            #discriminant_methods
+        }
+    });
+
+    let parse_error_doc = format!(" The error of parsing [`{name}`] from the name of a variant.");
+    let parse_error_message = format!("unknown variant name of `{enum_ident}`");
+
+    items.push(syn::parse_quote! {
+        #[doc = #parse_error_doc]
+        #[derive(
+            ::core::clone::Clone,
+            ::core::marker::Copy,
+            ::core::fmt::Debug,
+            ::core::cmp::PartialEq,
+            ::core::cmp::Eq,
+        )]
+        #vis struct #parse_error_name;
+    });
+
+    items.push(syn::parse_quote! {
+        impl ::core::fmt::Display for #parse_error_name {
+            fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                f.write_str(#parse_error_message)
+            }
+        }
+    });
+
+    items.push(syn::parse_quote! {
+        impl ::core::error::Error for #parse_error_name {}
+    });
+
+    items.push(syn::parse_quote! {
+        impl ::core::str::FromStr for #name {
+            type Err = #parse_error_name;
+
+            fn from_str(s: &str) -> ::core::result::Result<Self, Self::Err> {
+                match s {
+                    #(#variant_names => ::core::result::Result::Ok(Self::#variant_idents),)*
+                    _ => ::core::result::Result::Err(#parse_error_name),
+                }
+            }
+        }
+    });
+
+    // The skipped variants have no kind, so the kind of a value is only known for the others
+    let kind_method = match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            #[doc = concat!(" Returns the kind of the variant, i.e. [`", stringify!(#name), "`], or `None` if the variant is skipped.")]
+            #vis const fn kind(&self) -> ::core::option::Option<#name> {
+                match *self {
+                    #(#enum_ident::#variant_idents { .. } => ::core::option::Option::Some(#name::#variant_idents),)*
+                    #skipped_pattern => ::core::option::Option::None,
+                }
+            }
+        },
+        None => quote::quote! {
+            #[doc = concat!(" Returns the kind of the variant, i.e. [`", stringify!(#name), "`].")]
+            #vis const fn kind(&self) -> #name {
+                match *self {
+                    #(#enum_ident::#variant_idents { .. } => #name::#variant_idents,)*
+                }
+            }
+        },
+    };
+
+    items.push(syn::parse_quote! {
+        impl #enum_impl_generics #enum_ident #enum_ty_generics #enum_where_clause {
+            #kind_method
+        }
+    });
+
+    items
+}
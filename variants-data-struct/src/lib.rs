@@ -1,15 +1,59 @@
 #[doc = include_str!("../README.md")]
 use proc_macro::TokenStream;
 
+mod accessors;
+mod builder;
+mod columns;
+mod conversions;
+mod counters;
+mod defaults;
+mod enum_methods;
+mod family;
+mod field_wrapper;
+mod generics;
+mod handlers;
+mod kind;
+mod markers;
+mod missing_variants;
+mod options;
+mod original_enum;
+mod record;
+mod reflection;
+mod router;
+mod traits;
+mod uniform;
 mod variants_data_struct_attr_meta;
 mod variants_data_struct_defs;
 mod variants_data_struct_field_attr_meta;
 mod variants_data_struct_field_meta;
 mod variants_data_struct_meta;
+mod views;
+mod visitor;
 
+use crate::accessors::accessors;
+use crate::builder::builder;
+use crate::columns::columns;
+use crate::conversions::conversions;
+use crate::counters::counters;
+use crate::defaults::defaults;
+use crate::enum_methods::enum_methods;
+use crate::family::family;
+use crate::handlers::handlers;
+use crate::kind::kind;
+use crate::markers::markers;
+use crate::missing_variants::missing_variants;
+use crate::options::options;
+use crate::original_enum::OriginalEnum;
+use crate::record::record;
+use crate::reflection::reflection;
+use crate::router::router;
+use crate::traits::traits;
+use crate::uniform::uniform;
 use crate::variants_data_struct_attr_meta::VariantsDataStructAttrMeta;
 use crate::variants_data_struct_defs::{VariantsDataStructDefs, variants_data_struct_defs};
 use crate::variants_data_struct_meta::VariantsDataStructMeta;
+use crate::views::views;
+use crate::visitor::visitor;
 
 /// Derive macro to generate a data struct containing fields for each variant of the enum.
 ///
@@ -38,6 +82,63 @@ use crate::variants_data_struct_meta::VariantsDataStructMeta;
 /// // }
 /// ```
 ///
+/// Each variant type gets the `const fn new(...)` function, which takes its fields in the declaration order.
+/// The variant types of tuple variants also get the `into_inner(self)` method, which returns the only field
+/// or the tuple of the fields, and the conversions from and into the tuple of the fields, e.g.
+/// `impl From<(i32, String)> for TupleEnumVariantType` and `impl From<TupleEnumVariantType> for (i32, String)`.
+///
+/// ## Generics
+///
+/// The derived variants data struct has the same generics as the original enum. Each "variant type" only gets
+/// the lifetime, type, and const parameters of the original enum that are used by the fields of the respective variant,
+/// together with the bounds and where-clause predicates that only involve them.
+///
+/// Additional generic parameters and where-clause predicates can be specified with the `generics(<...>)` and
+/// `where(...)` items of the `variants_data_struct` attribute and the `variant_ty_generics(<...>)` and
+/// `variant_ty_where(...)` items of the `variants_data_struct_field` attribute.
+///
+/// If some lifetime or type parameters of the data struct or a variant type are not used by any of its fields
+/// (e.g. because of `field_ty_override`), the struct gets a hidden `_marker` field of type
+/// [`PhantomData`](core::marker::PhantomData) that uses them.
+///
+/// ```rust
+/// use variants_data_struct::VariantsDataStruct;
+///
+/// #[derive(VariantsDataStruct)]
+/// pub enum Msg<T: Clone, const N: usize> {
+///     Data(T),
+///     Batch([u8; N]),
+///     Empty,
+/// }
+///
+/// // Equivalent to:
+/// // pub struct MsgVariantsData<T: Clone, const N: usize> {
+/// //     pub data: DataVariantType<T>,
+/// //     pub batch: BatchVariantType<N>,
+/// //     pub empty: (),
+/// // }
+/// //
+/// // pub struct DataVariantType<T: Clone>(pub T);
+/// //
+/// // pub struct BatchVariantType<const N: usize>(pub [u8; N]);
+///
+/// #[derive(VariantsDataStruct)]
+/// pub enum Token<'a> {
+///     Ident(&'a str),
+///     Punct(char),
+/// }
+///
+/// // Equivalent to:
+/// // pub struct TokenVariantsData<'a> {
+/// //     pub ident: IdentVariantType<'a>,
+/// //     pub punct: PunctVariantType,
+/// // }
+/// //
+/// // pub struct IdentVariantType<'a>(pub &'a str);
+/// //
+/// // pub struct PunctVariantType(pub char);
+/// ```
+///
 /// ## Helper attributes
 ///
 /// ### `#[variants_data_struct(<meta>)]` customizes the behavior of the derive macro.
@@ -45,14 +146,174 @@ use crate::variants_data_struct_meta::VariantsDataStructMeta;
 /// The `<meta>` (see [`VariantsDataStructAttrMeta`](crate::variants_data_struct_attr_meta::VariantsDataStructAttrMeta))
 /// is a comma-separated list that can contain the following items:
 ///
-// - `attrs(#[derive(...)] ...)`: Adds the specified attributes to the generated data struct. Notably, you
-/// can use it to add derives like `Debug`, `Clone` to the generated struct.
+/// - `attrs(#[derive(...)] ...)`: Adds the specified attributes to the generated data struct. Notably, you
+///   can use it to add derives like `Debug`, `Clone` to the generated struct.
 /// - `vis = <visibility>`: Specifies a custom visibility for the generated data struct. If not provided,
-/// the visibility of the original enum is used.
+///   the visibility of the original enum is used.
 /// - `name = <CustomName>`: Specifies a custom name for the generated data struct.
-///  If not provided, the default name is `<EnumName>VariantsData`.
+///   If not provided, the default name is `<EnumName>VariantsData`.
 /// - `variants_tys_attrs(#[derive(...)] ...)`: Adds the specified attributes to each of the generated variant type structs.
-/// Notably, you can use it to add derives like `Debug`, `Clone` to the generated variant type structs.
+///   Notably, you can use it to add derives like `Debug`, `Clone` to the generated variant type structs.
+/// - `generics(<...>)`: Adds the specified generic parameters to the generated data struct, e.g. when
+///   `field_ty_override = Vec<U>` needs a `U` the original enum doesn't have.
+/// - `where(...)`: Adds the specified where-clause predicates to the generated data struct, e.g. `where(T: Clone)`.
+/// - `exclude_units`, `only(<Variant>, ...)` and `except(<Variant>, ...)`: Skip the unit variants, all variants but
+///   the listed ones, and the listed variants, respectively (see also the `skip` item of the `variants_data_struct_field`
+///   attribute). A skipped variant gets no field in the data struct, no variant type struct and no place in any of the
+///   generated companions, e.g. no kind, view, column or handler. The methods that take a value of the original enum
+///   become partial: `kind(&self)`, `as_variant_ref(&self)` and `as_variant_mut(&mut self)` return `None`,
+///   the `push`, `insert`, `handle` and `accept(self, visitor)` methods return the value back in `Err` (dropping it
+///   in the `Extend` implementations), `route` returns the `RouteError` with the `Skipped` reason, and `record` of the counters ignores the value.
+///   The uniform map doesn't implement `Index<&MyEnum>` and `IndexMut<&MyEnum>`, and the `Kind` of `HasVariantsData`
+///   (see the `traits` item) is `Option<MyEnumKind>`.
+/// - `field_wrapper = <Template<_>>`: Wraps the type of each field of the generated data struct with the template,
+///   e.g. `Option<_>`, `Vec<_>` or `Box<_>`, where `_` is replaced with the variant type struct, `()` or the
+///   `field_ty_override` of the respective variant.
+/// - `field_wrap = <expr>`: Specifies the function or closure that wraps the values of the fields with the `field_wrapper`
+///   template, e.g. `field_wrap = |value| vec![value]` for `Vec<_>`. If not provided, the values are wrapped with
+///   `Into::into` for the standard wrappers that implement `From<T>`, i.e. `Option<_>`, `Box<_>`, `Rc<_>`, `Arc<_>`,
+///   `Cell<_>`, `RefCell<_>`, `Mutex<_>` and `RwLock<_>`. For other templates, the values can't be constructed from
+///   the variants, so the methods that need it are omitted: `try_into_complete` of the `options`, `accept(self, visitor)`
+///   of the original enum with the `visitor` and the `<field_name>(...)` setter of the `builder`.
+/// - `crate = <path>`: Specifies the path to the `variants-data-struct-runtime` crate, which is used by
+///   the `traits`, `reflection`, `router`, `family` and `markers` items, e.g. `crate = ::my_crate::runtime` when it is re-exported.
+///   If not provided, the default path is `::variants_data_struct_runtime`.
+/// - `conversions`: Generates `impl From<VariantType> for MyEnum` and `impl TryFrom<MyEnum> for VariantType`
+///   for each of the generated variant type structs. The conversion from the original enum fails with the original enum
+///   as the error if it is a different variant.
+/// - `default`: Implements `Default` for the generated data struct and each of the generated variant type structs.
+///   Each field of the data struct is initialized with the `default = <expr>` of the respective variant, and each field
+///   of a variant type struct with the `default = <expr>` of the respective field of the variant (see below), if provided,
+///   and with `Default::default()` otherwise. Only the types of the fields initialized with `Default::default()`
+///   that mention generic parameters are required to implement `Default`.
+/// - `accessors` or `accessors(get = <vis>, get_mut = <vis>, set = <vis>, with = <vis>)`: Generates the
+///   `<field_name>(&self) -> &FieldType`, `<field_name>_mut(&mut self) -> &mut FieldType`,
+///   `set_<field_name>(&mut self, value: FieldType) -> FieldType` (returning the previous value) and
+///   `with_<field_name>(self, value: FieldType) -> Self` methods of the data struct for each of its fields,
+///   which is useful when the fields are less visible than the data struct (see `field_vis` below).
+///   Each kind of accessor has the provided visibility, if any, and that of the data struct otherwise.
+/// - `enum_methods`: Generates the `is_<field_name>(&self)`, `as_<field_name>(&self)`, `as_<field_name>_mut(&mut self)`,
+///   `into_<field_name>(self)` and `map_<field_name>(self, f)` methods of the original enum for each variant, where
+///   `<field_name>` is the name of the respective field in the data struct. The `as_*` methods return the references
+///   to the variant's fields, while the `into_*` and `map_*` methods work with the variant type struct, if it is generated,
+///   and with the fields (as a single value or a tuple) otherwise.
+/// - `visitor` or `visitor(name = <VisitorName>, mut_name = <VisitorMutName>)`: Generates the
+///   `<EnumName>VariantsVisitor` and `<EnumName>VariantsVisitorMut` traits (unless custom names are provided)
+///   with the generics of the generated data struct and a `visit_<field_name>(&mut self, value: &FieldType)`
+///   (respectively, `visit_<field_name>_mut(&mut self, value: &mut FieldType)`) method for each of its fields,
+///   which does nothing by default. The generated data struct gets the `accept(&self, visitor)` and
+///   `accept_mut(&mut self, visitor)` methods, which visit each field in the order of the variants, and the original
+///   enum gets the `accept(self, visitor)` method, which visits the data of its variant converted into the type of the field.
+///   The `accept(self, visitor)` method is omitted if the value of some field can't be constructed from the variant,
+///   i.e. for a `field_ty_override` without `field_from` or a `field_wrapper` without a known `field_wrap`.
+/// - `handlers` or `handlers(name = <HandlersName>, attrs(...))`: Generates the `<EnumName>Handlers<'a, R>` struct
+///   (unless a custom name is provided) with a `Box<dyn FnMut(Data) -> R + 'a>` field for each variant, named like the
+///   respective field of the data struct, where `Data` is the variant type struct, if it is generated, or the variant's
+///   fields (as a single value or a tuple) otherwise. The struct has the `new(...)` function taking every handler,
+///   the `handle(&mut self, value)` method, which calls the handler of the variant of the value, and implements `Default`
+///   (returning `R::default()` from every handler) for `R: Default`. Its `builder()` function returns the
+///   `<HandlersName>Builder` with a setter for each handler and the `build(self)` method, which returns the
+///   `<EnumName>MissingVariants` error if some handlers are not set, as well as the `build_or_default(self)` and
+///   `build_or_panic(self)` methods, whose missing handlers return `R::default()` and panic, respectively.
+/// - `router` or `router(name = <RouterName>, receivers_name = <ReceiversName>, attrs(...))`: Generates the
+///   `<EnumName>Router` struct (unless a custom name is provided) with a `std::sync::mpsc::Sender` field for each variant,
+///   named like the respective field of the data struct, of the variant type struct, if it is generated, or the variant's
+///   fields (as a single value or a tuple) otherwise, and the `<EnumName>Receivers` struct with the matching `Receiver` fields.
+///   The router implements `Clone` and has the `channel()` function, which returns the router and the receivers,
+///   and the `route(&self, value)` method, which sends the data of the value to the receiver of its variant or returns
+///   the `RouteError<MyEnum>` with the value and the `Disconnected` reason if the receiver is disconnected. The attributes in `attrs(...)` are added
+///   to both structs. Requires the `variants-data-struct-runtime` crate as a dependency.
+/// - `counters` or `counters(name = <CountersName>, attrs(...))`: Generates the `<EnumName>Counters` struct
+///   (unless a custom name is provided) with an `AtomicU64` field for each variant, named like the respective field
+///   of the data struct. The struct implements `Default` and has the `const fn new()` function, the `record(&self, value)`
+///   method, which increments the counter of the variant of the value, the `snapshot(&self)` method, which returns
+///   the current counts as the `<EnumName>Map<u64>`, and the `reset(&self)` method. Implies `uniform`.
+/// - `builder` or `builder(name = <BuilderName>, typestate, attrs(...))`: Generates the `<VariantsDataStructName>Builder`
+///   struct (unless a custom name is provided) and the `builder()` function of the data struct. For each field of the
+///   data struct, the builder has the `<field_name>(...)` setter, which takes the fields of the variant, e.g.
+///   `.tuple_enum(42, "x")`, or, for struct variants with a variant type, a closure over the builder of the variant type,
+///   e.g. `.struct_enum(|b| b.id(1).name("n"))`, and the `set_<field_name>(value)` setter, which takes the value of the field.
+///   The `build(self)` method returns the data struct or the `<EnumName>MissingVariants` error listing the names of
+///   the variants whose fields aren't set. With `typestate`, the builder has a type parameter for each field, which is `()`
+///   until the field is set, and `build(self)` is only available (and returns the data struct) once every field is set.
+///   The builders of the variant types (e.g. `StructEnumVariantTypeBuilder`) always track the set fields this way.
+///   The setters of the fields of the variants take `impl Into<FieldType>`, except for the primitive numeric types,
+///   which are taken as is, so that their literals are inferred, e.g. `.tuple_enum(42, "x")` for `TupleEnum(i32, String)`.
+///   The names of the setters and `build` must not conflict, e.g. a struct variant can't have a field named `build`.
+///   The `<field_name>(...)` setter is omitted for the fields whose values can't be constructed from the variant,
+///   i.e. for a `field_ty_override` without `field_from` or a `field_wrapper` without a known `field_wrap`.
+/// - `traits`: Implements the `HasVariantsData` trait of the
+///   [`variants-data-struct-runtime`](https://docs.rs/variants-data-struct-runtime) crate for the original enum,
+///   with the generated data struct as its `Data`, the kind enum as its `Kind` and the number of variants as its
+///   `VARIANT_COUNT`, and the `VariantOf<MyEnum>` trait for each of the generated variant type structs, with the index
+///   of the variant as its `INDEX` and the `into_enum(self)` method. If some variants are skipped, the `Kind` is
+///   `Option<MyEnumKind>`, and the `VARIANT_COUNT` and the indices only count the variants that aren't skipped. Implies `kind` and requires the
+///   `variants-data-struct-runtime` crate as a dependency. Can't be used with additional generic parameters of the data struct.
+/// - `reflection`: Generates the `VARIANTS` constant of the original enum with the `VariantInfo` of each variant, i.e.
+///   its name, the name of its field in the generated data struct, the name of its variant type struct (if generated),
+///   its shape (unit, tuple or struct), the names of its fields and the names of their types (as given by
+///   `core::any::type_name`, with the generic parameters of the enum substituted), and its discriminant.
+///   The generated data struct gets the `field_names()` function and the `get_by_name(&self, name)` method, which returns
+///   the field with the given name as `&dyn Any` (only if the data struct is `'static`). Requires the
+///   `variants-data-struct-runtime` crate as a dependency.
+/// - `views` or `views(ref_name = <RefName>, mut_name = <MutName>, attrs(...))`: Generates the borrowed and
+///   mutably borrowed views of the original enum, i.e. the `<EnumName>Ref<'a>` and `<EnumName>Mut<'a>` enums
+///   (unless custom names are provided) whose variants hold the `<VariantName>VariantRef<'a>` and
+///   `<VariantName>VariantMut<'a>` view structs of the generated variant types, and the
+///   `as_variant_ref(&self)` and `as_variant_mut(&mut self)` methods of the original enum.
+///   The attributes in `attrs(...)` are added to each of the generated views.
+/// - `kind` or `kind(name = <KindName>, vis = <visibility>, derives(...), attrs(...))`: Generates the fieldless
+///   `<EnumName>Kind` enum (unless a custom name is provided) with the same variants as the original enum, which derives
+///   `Clone`, `Copy`, `Debug`, `PartialEq`, `Eq`, `Hash`, `PartialOrd`, `Ord` and the derives in `derives(...)`.
+///   The kind enum has the `ALL` and `COUNT` constants, the `name()` method and a `FromStr` implementation
+///   (with the `Parse<KindName>Error` error), and the original enum gets the `kind(&self)` method.
+///   If the original enum has explicit discriminants, the kind enum gets them (and the primitive `repr`) too,
+///   together with the `discriminant()` and `from_discriminant(...)` methods. Unless `vis` is provided,
+///   the kind enum and its methods have the visibility of the generated data struct.
+/// - `options` or `options(name = <OptionsName>, attrs(...))`: Generates the `<EnumName>VariantsOptions` struct
+///   (unless a custom name is provided) with an `Option` field for each variant that holds the variant type struct,
+///   if it is generated, or the variant's fields (as a single value or a tuple) otherwise. The struct implements
+///   `Default`, `Extend<MyEnum>` and `FromIterator<MyEnum>` and has the `insert(&mut self, value)` method, which
+///   returns the previously stored value of the same variant, and the `try_into_complete(self)` method, which
+///   returns the data struct or the `<EnumName>MissingVariants` error listing the names of the missing variants.
+///   With `field_ty_override`, the stored fields are converted into the field type with `field_from`, and with
+///   `field_wrapper`, they are wrapped with `field_wrap`.
+/// - `columns` or `columns(name = <ColumnsName>, attrs(...))`: Generates the `<EnumName>Columns` struct
+///   (unless a custom name is provided) that stores the values of the original enum as a `Vec` for each variant
+///   (of the values of the respective fields of the generated data struct) and a compact vector of tags that keeps
+///   their order.
+///   The struct implements `Default`, `Extend<MyEnum>` and `FromIterator<MyEnum>` and has the `push(&mut self, value)`,
+///   `len(&self)`, `is_empty(&self)`, `iter(&self)` (yielding the borrowed views in order) and `into_enums(self)` methods,
+///   as well as a `<field_name>(&self)` method returning the slice of each variant's data. Implies `views`.
+///   Since `iter(&self)` and `into_enums(self)` convert the values back into the original enum, `columns` can't be
+///   combined with `field_ty_override` or `field_wrapper`. The field names must differ from `tags` and the names of
+///   the methods above (see the `field_name` item of the `variants_data_struct_field` attribute). Requires `std`.
+/// - `uniform` or `uniform(name = <MapName>, attrs(...))`: Generates the `<EnumName>Map<T>` struct (unless a custom name
+///   is provided) with a field of type `T` for each variant, named like the respective field of the data struct.
+///   The struct implements `Default`, `Index<&MyEnum>` and `IndexMut<&MyEnum>` (keyed by the variant of the value)
+///   and has the `from_fn(f)` (called with the names of the variants), `map(self, f)`, `zip(self, other)` and
+///   `iter(&self)` (yielding the names of the variants and the references to their values) methods.
+/// - `family` or `family(name = <FamilyName>, attrs(...))`: Generates the `<EnumName>VariantsFamily<'f, F>` struct
+///   (unless a custom name is provided), where `F` implements the `VariantFamily` trait of the
+///   [`variants-data-struct-runtime`](https://docs.rs/variants-data-struct-runtime) crate, with a field of type
+///   `F::Apply<'f, FieldType>` for each field of the generated data struct. For example, `OwnedFamily`, `OptionFamily`,
+///   `VecFamily` and `RefFamily` give the fields of types `FieldType`, `Option<FieldType>`, `Vec<FieldType>` and
+///   `&'f FieldType`, respectively. The struct has the `map_family(self, mapper)` method, which maps each field with
+///   a `FamilyMapper`, and converts from and into the data struct with the `OwnedFamily`.
+///   Requires the `variants-data-struct-runtime` crate as a dependency.
+/// - `record` or `record(name = <RecordName>, attrs(...))`: Generates the `<EnumName>VariantsRecord` struct
+///   (unless a custom name is provided) with a type parameter for each field of the generated data struct, named
+///   after the respective variant and defaulting to the type of the field, e.g.
+///   `MyEnumVariantsRecord<UnitEnum = (), TupleEnum = TupleEnumVariantType, StructEnum = StructEnumVariantType>`.
+///   The struct has the `map_<field_name>(self, f)` method for each field, which changes the type of only that field,
+///   and converts from and into the data struct with the default type parameters.
+/// - `markers` or `markers(mod_name = <module_name>, attrs(...))`: Generates the `<enum_name>_kinds` module
+///   (unless a custom name is provided) with a zero-sized marker type for each variant, named like the variant, e.g.
+///   `my_enum_kinds::TupleEnum`, and implements `Field<my_enum_kinds::TupleEnum>` for the generated data struct,
+///   whose `Output` is the type of the respective field and which has the `get(&self)`, `get_mut(&mut self)` and
+///   `into_field(self)` methods. The marker types implement `VariantMarker` with the name of the variant
+///   and the attributes in `attrs(...)` are added to each of them. Both traits come from the
+///   `variants-data-struct-runtime` crate, which is required as a dependency.
 ///
 /// ### `#[variants_data_struct_field(<meta>)]` customizes the behavior of individual fields in the generated data struct
 /// and their corresponding variant types.
@@ -60,19 +321,44 @@ use crate::variants_data_struct_meta::VariantsDataStructMeta;
 /// The `<meta>` (see [`VariantsDataStructFieldAttrMeta`](crate::variants_data_struct_field_attr_meta::VariantsDataStructFieldAttrMeta))
 /// is a comma-separated list that can contain the following items:
 ///
+/// - `skip`: Skips the variant, i.e. generates no field in the data struct for it (see the `exclude_units`,
+///   `only(...)` and `except(...)` items of the `variants_data_struct` attribute). The other items are ignored.
 /// - `field_attrs(#[derive(...)] ...)`: Adds the specified attributes to the generated field in the data struct.
-/// Notably, you can use it to add derives like `Debug`, `Clone` to
-/// the generated field.
+///   Notably, you can use it to add derives like `Debug`, `Clone` to
+///   the generated field.
 /// - `field_vis = <visibility>`: Specifies a custom visibility for the generated field in the data struct. If not provided,
-/// the visibility of the generated data struct is used.
+///   the visibility of the generated data struct is used.
 /// - `field_name = <custom_field_name>`: Specifies a custom name for the generated field in the data struct. If not provided,
-/// the name is derived from the original variant's name (converted to `snake_case`).
+///   the name is derived from the original variant's name (converted to `snake_case`).
 /// - `field_ty_override`: Overrides the type of the generated field in the data struct. If not provided,
-/// the type is derived from the original variant's fields. For variants without fields (a unit variant or a struct or tuple variant with no fields),
-/// the type is `()`. For tuple and struct variants, a separate "variant type" struct is generated to encapsulate the fields.
+///   the type is derived from the original variant's fields. For variants without fields (a unit variant or a struct or tuple variant with no fields),
+///   the type is `()`. For tuple and struct variants, a separate "variant type" struct is generated to encapsulate the fields.
+/// - `field_from = <expr>`: Specifies the function or closure that converts the fields of the variant (the value of
+///   the only field or the tuple of the values of the fields, `()` for a unit variant) into the `field_ty_override`,
+///   e.g. `field_from = u32::from`. If not provided, the value of the field can't be constructed from the variant,
+///   so the methods that need it are omitted (see the `field_wrap` item of the `variants_data_struct` attribute).
 /// - `gen_variant_ty`: Overrides the decision whether to generate a separate "variant type" struct for the variant.
-/// If not provided, a "variant type" struct is generated for tuple and struct variants, and not for unit variants. If `field_ty_override` is provided,
-/// by default, no "variant type" struct is generated.
+///   If not provided, a "variant type" struct is generated for tuple and struct variants, and not for unit variants. If `field_ty_override` is provided,
+///   by default, no "variant type" struct is generated.
+/// - `variant_ty_generics(<...>)`: Adds the specified generic parameters to the generated variant type struct.
+///   If a parameter is already inferred, only its bounds are added.
+/// - `variant_ty_where(...)`: Adds the specified where-clause predicates to the generated variant type struct.
+/// - `field_wrapper = <Template<_>>`: Overrides the `field_wrapper` item of the `variants_data_struct` attribute
+///   for the field. `field_wrapper = _` disables the wrapping of the field.
+/// - `field_wrap = <expr>`: Overrides the `field_wrap` item of the `variants_data_struct` attribute for the field.
+///   With `field_wrapper` of the field, the `field_wrap` of the `variants_data_struct` attribute doesn't apply.
+/// - `deref = <bool>`: Implements `Deref`, `DerefMut` and `AsRef` to the only field of the generated variant type struct
+///   if `true`. Requires the variant type struct to be generated for a variant with a single field.
+/// - `conversions = <bool>`: Overrides the decision whether to generate the conversions between the original enum
+///   and the variant type struct. If not provided, the `conversions` item of the `variants_data_struct` attribute is used.
+/// - `default = <expr>`: Specifies the value of the generated field in the `Default` implementation of the data struct
+///   (see the `default` item of the `variants_data_struct` attribute). The `#[variants_data_struct_field(default = <expr>)]`
+///   attribute can also be applied to the fields of a variant, where `default` is the only supported item, to specify
+///   the value of the respective field in the `Default` implementation of the variant type struct.
+/// - `accessors` or `accessors(get = <vis>, get_mut = <vis>, set = <vis>, with = <vis>)`: Generates the accessors
+///   of the generated field (see the `accessors` item of the `variants_data_struct` attribute), even if they are
+///   not requested for the whole data struct. The visibilities that aren't provided are those of the `accessors` item
+///   of the `variants_data_struct` attribute, if any, and that of the data struct otherwise.
 #[proc_macro_derive(
     VariantsDataStruct,
     attributes(variants_data_struct, variants_data_struct_field)
@@ -100,39 +386,339 @@ pub fn derive_variants_data_struct(item: TokenStream) -> TokenStream {
 
     // Parse the `variants_data_struct` attribute meta
     let variants_data_struct_attr_meta: VariantsDataStructAttrMeta =
-        match VariantsDataStructAttrMeta::from_attrs(attrs).map(Option::unwrap_or_default) {
+        match VariantsDataStructAttrMeta::from_attrs(&attrs).map(Option::unwrap_or_default) {
             Ok(meta) => meta,
             Err(err) => return err.to_compile_error().into(),
         };
 
     // Resolve the final metadata for the derived variants data struct
+    let variants_data_struct_meta =
+        VariantsDataStructMeta::resolve(variants_data_struct_attr_meta, &ident, &vis, &generics);
+
+    // Resolve the final metadata for the fields of the data struct and the variant types
+    let original_enum = match OriginalEnum::resolve(
+        &attrs,
+        ident,
+        generics,
+        enum_data.variants,
+        &variants_data_struct_meta,
+    ) {
+        Ok(original_enum) => original_enum,
+        Err(err) => return err.to_compile_error().into(),
+    };
+
+    // Generate the conversions between the original enum and the variant types
+    let conversions = conversions(&original_enum);
+
+    // Generate the per-variant methods of the original enum, if requested
+    let enum_methods = variants_data_struct_meta
+        .enum_methods
+        .then(|| enum_methods(&variants_data_struct_meta.vis, &original_enum));
+
+    // Generate the borrowed and mutably borrowed views of the original enum, if requested
+    let views = match &variants_data_struct_meta.views {
+        Some(views_meta) => match views(views_meta, &variants_data_struct_meta.vis, &original_enum)
+        {
+            Ok(views) => views,
+            Err(err) => return err.to_compile_error().into(),
+        },
+        None => vec![],
+    };
+
+    // Generate the kind enum of the original enum, if requested
+    let kind = variants_data_struct_meta
+        .kind
+        .as_ref()
+        .map(|kind_meta| kind(kind_meta, &original_enum))
+        .unwrap_or_default();
+
+    // Generate the options struct of the original enum, if requested
+    let options = variants_data_struct_meta
+        .options
+        .as_ref()
+        .map(|options_meta| {
+            options(
+                options_meta,
+                &variants_data_struct_meta.vis,
+                &variants_data_struct_meta.name,
+                &variants_data_struct_meta.generics,
+                &original_enum,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the columns struct of the original enum, if requested
+    let columns = match (
+        &variants_data_struct_meta.columns,
+        &variants_data_struct_meta.views,
+    ) {
+        (Some(columns_meta), Some(views_meta)) => match columns(
+            columns_meta,
+            views_meta,
+            &variants_data_struct_meta.vis,
+            &variants_data_struct_meta.generics,
+            &original_enum,
+        ) {
+            Ok(columns) => columns,
+            Err(err) => return err.to_compile_error().into(),
+        },
+        _ => vec![],
+    };
+
+    // Generate the uniform map struct of the original enum, if requested
+    let uniform = variants_data_struct_meta
+        .uniform
+        .as_ref()
+        .map(|uniform_meta| uniform(uniform_meta, &variants_data_struct_meta.vis, &original_enum))
+        .unwrap_or_default();
+
+    // Generate the family struct of the original enum, if requested
+    let family = variants_data_struct_meta
+        .family
+        .as_ref()
+        .map(|family_meta| {
+            family(
+                family_meta,
+                &variants_data_struct_meta.vis,
+                &variants_data_struct_meta.name,
+                &variants_data_struct_meta.generics,
+                &original_enum,
+                &variants_data_struct_meta.runtime,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the record struct of the original enum, if requested
+    let record = variants_data_struct_meta
+        .record
+        .as_ref()
+        .map(|record_meta| {
+            record(
+                record_meta,
+                &variants_data_struct_meta.vis,
+                &variants_data_struct_meta.name,
+                &variants_data_struct_meta.generics,
+                &original_enum,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the marker types of the variants of the original enum, if requested
+    let markers = variants_data_struct_meta
+        .markers
+        .as_ref()
+        .map(|markers_meta| {
+            markers(
+                markers_meta,
+                &variants_data_struct_meta.vis,
+                &variants_data_struct_meta.name,
+                &variants_data_struct_meta.generics,
+                &original_enum,
+                &variants_data_struct_meta.runtime,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the visitor traits of the derived variants data struct, if requested
+    let visitor = variants_data_struct_meta
+        .visitor
+        .as_ref()
+        .map(|visitor_meta| {
+            visitor(
+                visitor_meta,
+                &variants_data_struct_meta.vis,
+                &variants_data_struct_meta.name,
+                &variants_data_struct_meta.generics,
+                &original_enum,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the handlers struct of the original enum, if requested
+    let handlers = variants_data_struct_meta
+        .handlers
+        .as_ref()
+        .map(|handlers_meta| {
+            handlers(
+                handlers_meta,
+                &variants_data_struct_meta.vis,
+                &original_enum,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the router of the original enum, if requested
+    let router = variants_data_struct_meta
+        .router
+        .as_ref()
+        .map(|router_meta| {
+            router(
+                router_meta,
+                &variants_data_struct_meta.vis,
+                &original_enum,
+                &variants_data_struct_meta.runtime,
+            )
+        })
+        .unwrap_or_default();
+
+    // Generate the counters struct of the original enum, if requested
+    let counters = match (
+        &variants_data_struct_meta.counters,
+        &variants_data_struct_meta.uniform,
+    ) {
+        (Some(counters_meta), Some(uniform_meta)) => counters(
+            counters_meta,
+            uniform_meta,
+            &variants_data_struct_meta.vis,
+            &original_enum,
+        ),
+        _ => vec![],
+    };
+
+    // Generate the builder of the derived variants data struct, if requested
+    let builder = match &variants_data_struct_meta.builder {
+        Some(builder_meta) => match builder(
+            builder_meta,
+            &variants_data_struct_meta.vis,
+            &variants_data_struct_meta.name,
+            &variants_data_struct_meta.generics,
+            &original_enum,
+        ) {
+            Ok(builder) => builder,
+            Err(err) => return err.to_compile_error().into(),
+        },
+        None => vec![],
+    };
+
+    // Generate the implementations of the traits of the runtime crate, if requested
+    let traits = match (
+        variants_data_struct_meta.traits,
+        &variants_data_struct_meta.kind,
+    ) {
+        (true, Some(kind_meta)) => match traits(
+            kind_meta,
+            &variants_data_struct_meta.name,
+            &variants_data_struct_meta.generics,
+            &original_enum,
+            &variants_data_struct_meta.runtime,
+        ) {
+            Ok(traits) => traits,
+            Err(err) => return err.to_compile_error().into(),
+        },
+        _ => vec![],
+    };
+
+    // Generate the reflection metadata of the original enum, if requested
+    let reflection = if variants_data_struct_meta.reflection {
+        reflection(
+            &variants_data_struct_meta.vis,
+            &variants_data_struct_meta.name,
+            &variants_data_struct_meta.generics,
+            &original_enum,
+            &variants_data_struct_meta.runtime,
+        )
+    } else {
+        vec![]
+    };
+
+    // Generate the `Default` implementations of the data struct and the variant types, if requested
+    let defaults = if variants_data_struct_meta.default {
+        defaults(
+            &variants_data_struct_meta.name,
+            &variants_data_struct_meta.generics,
+            &original_enum,
+        )
+    } else {
+        vec![]
+    };
+
+    // Generate the accessors of the fields of the data struct, if requested for any of them
+    let accessors = accessors(
+        &variants_data_struct_meta.name,
+        &variants_data_struct_meta.generics,
+        &original_enum,
+    );
+
+    // Generate the error type listing the missing variants, if a companion type needs it
+    let missing_variants = if variants_data_struct_meta.options.is_some()
+        || variants_data_struct_meta.handlers.is_some()
+        || variants_data_struct_meta
+            .builder
+            .as_ref()
+            .is_some_and(|builder_meta| !builder_meta.typestate)
+    {
+        missing_variants(&variants_data_struct_meta.vis, &original_enum.ident)
+    } else {
+        vec![]
+    };
+
     let VariantsDataStructMeta {
         attrs: variants_data_struct_attrs,
         vis: variants_data_struct_vis,
         name: variants_data_struct_name,
-        variants_tys_attrs,
-    } = VariantsDataStructMeta::resolve(variants_data_struct_attr_meta, &ident, &vis);
+        generics: variants_data_struct_generics,
+        ..
+    } = variants_data_struct_meta;
 
     // Generate the variants data struct definitions
     let VariantsDataStructDefs {
         derived_struct,
         variant_type_structs,
-    } = match variants_data_struct_defs(
+        variant_type_impls,
+    } = variants_data_struct_defs(
         variants_data_struct_attrs,
-        variants_tys_attrs,
         variants_data_struct_vis,
         variants_data_struct_name,
-        generics,
-        enum_data.variants,
-    ) {
-        Ok(defs) => defs,
-        Err(err) => return err.to_compile_error().into(),
-    };
+        variants_data_struct_generics,
+        &original_enum.variants,
+    );
 
     quote::quote! {
         #derived_struct
 
         #(#variant_type_structs)*
+
+        #(#variant_type_impls)*
+
+        #(#conversions)*
+
+        #enum_methods
+
+        #(#views)*
+
+        #(#kind)*
+
+        #(#options)*
+
+        #(#columns)*
+
+        #(#uniform)*
+
+        #(#family)*
+
+        #(#record)*
+
+        #(#markers)*
+
+        #(#visitor)*
+
+        #(#handlers)*
+
+        #(#router)*
+
+        #(#counters)*
+
+        #(#builder)*
+
+        #(#traits)*
+
+        #(#reflection)*
+
+        #(#defaults)*
+
+        #(#accessors)*
+
+        #(#missing_variants)*
     }
     .into()
 }
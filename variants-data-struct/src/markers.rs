@@ -0,0 +1,185 @@
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `markers(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`MarkersMeta`].
+#[derive(Default)]
+pub(crate) struct MarkersAttrMeta {
+    /// The override for the name of the module of the marker types.
+    ///
+    /// If not provided, the default name is `<enum_name>_kinds`.
+    pub(crate) mod_name: Option<syn::Ident>,
+    /// The attributes to be applied to each marker type.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for MarkersAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut mod_name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "mod_name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let mod_name_ident: syn::Ident = input.parse()?;
+                    mod_name = Some(mod_name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in markers of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(MarkersAttrMeta { mod_name, attrs })
+    }
+}
+
+/// The resolved metadata for the marker types of the variants of the original enum.
+///
+/// For the raw attribute meta, see [`MarkersAttrMeta`].
+pub(crate) struct MarkersMeta {
+    /// The name of the module of the marker types.
+    pub(crate) mod_name: syn::Ident,
+    /// The attributes to be applied to each marker type.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl MarkersMeta {
+    pub(crate) fn resolve(attr_meta: MarkersAttrMeta, enum_ident: &syn::Ident) -> MarkersMeta {
+        use convert_case::Casing as _;
+
+        let MarkersAttrMeta { mod_name, attrs } = attr_meta;
+
+        let mod_name = match mod_name {
+            Some(mod_name) => mod_name,
+            None => syn::Ident::new(
+                &format!(
+                    "{}_kinds",
+                    enum_ident
+                        .to_string()
+                        .from_case(convert_case::Case::Pascal)
+                        .to_case(convert_case::Case::Snake)
+                ),
+                enum_ident.span(),
+            ),
+        };
+
+        MarkersMeta { mod_name, attrs }
+    }
+}
+
+/// Generates the zero-sized marker types of the variants of the original enum, i.e.
+///
+/// * the `my_enum_kinds` module with a marker type for each variant, e.g. `my_enum_kinds::TupleEnum`,
+///   implementing `VariantMarker`,
+/// * the `Field<my_enum_kinds::TupleEnum>` implementation of the derived variants data struct for each variant.
+pub(crate) fn markers(
+    // The resolved metadata for the marker types.
+    markers_meta: &MarkersMeta,
+    // The visibility of the module of the marker types, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+    // The path to the `variants-data-struct-runtime` crate.
+    runtime: &syn::Path,
+) -> Vec<syn::Item> {
+    let MarkersMeta { mod_name, attrs } = markers_meta;
+
+    let enum_ident = &original_enum.ident;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let marker_structs = original_enum.variants.iter().map(|variant| {
+        let ident = &variant.ident;
+        let doc =
+            format!(" The marker type of [`{enum_ident}::{ident}`](super::{enum_ident}::{ident}).");
+        quote::quote! {
+            #[doc = #doc]
+            #[derive(
+                ::core::clone::Clone,
+                ::core::marker::Copy,
+                ::core::fmt::Debug,
+                ::core::default::Default,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+                ::core::hash::Hash,
+                ::core::cmp::PartialOrd,
+                ::core::cmp::Ord,
+            )]
+            #(#attrs)*
+            pub struct #ident;
+        }
+    });
+
+    let doc = format!(" The zero-sized marker types of the variants of [`{enum_ident}`].");
+
+    let mut items: Vec<syn::Item> = vec![syn::parse_quote! {
+        #[doc = #doc]
+        #vis mod #mod_name {
+            #(#marker_structs)*
+        }
+    }];
+
+    // The implementations are outside of the module so that a relative path to the runtime crate can be used
+    items.extend(original_enum.variants.iter().map(|variant| -> syn::Item {
+        let ident = &variant.ident;
+        let name = ident.to_string();
+        syn::parse_quote! {
+            impl #runtime::VariantMarker for #mod_name::#ident {
+                const NAME: &'static str = #name;
+            }
+        }
+    }));
+
+    items.extend(original_enum.variants.iter().map(|variant| -> syn::Item {
+        let ident = &variant.ident;
+        let field_name = &variant.field_meta.field_name;
+        let field_ty = &variant.field_meta.field_ty;
+        syn::parse_quote! {
+            impl #impl_generics #runtime::Field<#mod_name::#ident>
+                for #variants_data_struct_name #ty_generics #where_clause
+            {
+                type Output = #field_ty;
+
+                fn get(&self) -> &Self::Output {
+                    &self.#field_name
+                }
+
+                fn get_mut(&mut self) -> &mut Self::Output {
+                    &mut self.#field_name
+                }
+
+                fn into_field(self) -> Self::Output {
+                    self.#field_name
+                }
+            }
+        }
+    }));
+
+    items
+}
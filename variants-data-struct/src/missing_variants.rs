@@ -0,0 +1,51 @@
+/// Returns the name of the error type that lists the variants of the original enum
+/// missing from a companion type, e.g. `MyEnumMissingVariants`.
+pub(crate) fn missing_variants_name(enum_ident: &syn::Ident) -> syn::Ident {
+    quote::format_ident!("{}MissingVariants", enum_ident)
+}
+
+/// Generates the error type that lists the variants of the original enum missing from a companion type,
+/// e.g. `MyEnumMissingVariants`, together with its `Display` and `Error` implementations.
+pub(crate) fn missing_variants(
+    // The visibility of the error type.
+    vis: &syn::Visibility,
+    // The name of the original enum.
+    enum_ident: &syn::Ident,
+) -> Vec<syn::Item> {
+    let name = missing_variants_name(enum_ident);
+    let doc = format!(" The error listing the variants of [`{enum_ident}`] that are missing.");
+    let message = format!("missing variants of `{enum_ident}`: ");
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #[derive(
+                ::core::clone::Clone,
+                ::core::fmt::Debug,
+                ::core::cmp::PartialEq,
+                ::core::cmp::Eq,
+            )]
+            #vis struct #name {
+                /// The names of the missing variants.
+                #vis variants: ::std::vec::Vec<&'static str>,
+            }
+        },
+        syn::parse_quote! {
+            impl ::core::fmt::Display for #name {
+                fn fmt(&self, f: &mut ::core::fmt::Formatter<'_>) -> ::core::fmt::Result {
+                    f.write_str(#message)?;
+                    for (i, variant) in self.variants.iter().enumerate() {
+                        if i > 0 {
+                            f.write_str(", ")?;
+                        }
+                        f.write_str(variant)?;
+                    }
+                    ::core::result::Result::Ok(())
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl ::core::error::Error for #name {}
+        },
+    ]
+}
@@ -0,0 +1,304 @@
+use crate::generics::{UsedGenericParams, fresh_type_param, phantom_marker_ident};
+use crate::missing_variants::missing_variants_name;
+use crate::original_enum::OriginalEnum;
+use crate::variants_data_struct_defs::variants_data_struct_marker_ty;
+
+/// The meta of the `options(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`OptionsMeta`].
+#[derive(Default)]
+pub(crate) struct OptionsAttrMeta {
+    /// The override for the name of the options struct.
+    ///
+    /// If not provided, the default name is `<EnumName>VariantsOptions`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the options struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for OptionsAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in options of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(OptionsAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the options struct of the original enum.
+///
+/// For the raw attribute meta, see [`OptionsAttrMeta`].
+pub(crate) struct OptionsMeta {
+    /// The name of the options struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the options struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl OptionsMeta {
+    pub(crate) fn resolve(attr_meta: OptionsAttrMeta, enum_ident: &syn::Ident) -> OptionsMeta {
+        let OptionsAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}VariantsOptions"), enum_ident.span()),
+        };
+
+        OptionsMeta { name, attrs }
+    }
+}
+
+/// Generates the options struct of the original enum, i.e.
+///
+/// * the `MyEnumVariantsOptions` struct with an `Option` of the "payload" of each variant
+///   (see [`OriginalVariant::payload_pattern`](crate::original_enum::OriginalVariant::payload_pattern)),
+/// * its `Default`, `Extend<MyEnum>` and `FromIterator<MyEnum>` implementations,
+/// * its `insert` and `try_into_complete` methods, where the latter is omitted if the value of some field
+///   of the derived variants data struct can't be constructed (see
+///   [`OriginalVariant::field_value_expr`](crate::original_enum::OriginalVariant::field_value_expr)).
+pub(crate) fn options(
+    // The resolved metadata for the options struct.
+    options_meta: &OptionsMeta,
+    // The visibility of the options struct, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct, which are also the generics of the options struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let OptionsMeta { name, attrs } = options_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+    let missing_variants_name = missing_variants_name(enum_ident);
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let variant_names: Vec<String> = variants
+        .iter()
+        .map(|variant| variant.ident.to_string())
+        .collect();
+    let payload_tys: Vec<syn::Type> = variants
+        .iter()
+        .map(|variant| variant.payload_ty())
+        .collect();
+    let count = variants.len();
+
+    // Mark the generic parameters of the options struct that no payload uses
+    let mut used_generic_params = UsedGenericParams::new(generics);
+    payload_tys
+        .iter()
+        .for_each(|payload_ty| used_generic_params.visit_type(payload_ty));
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+    let data_marker_init = variants_data_struct_marker_ty(generics, variants)
+        .map(|_marker_ty| quote::quote!(#marker_ident: ::core::marker::PhantomData,));
+
+    let fields = variants
+        .iter()
+        .zip(&payload_tys)
+        .map(|(variant, payload_ty)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(
+                " The data of the last inserted [`{enum_ident}::{}`], if any.",
+                variant.ident
+            );
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: ::core::option::Option<#payload_ty>
+            }
+        });
+
+    let insert_arms = variants.iter().map(|variant| {
+        let field_name = &variant.field_meta.field_name;
+        let pattern = variant.pattern(enum_ident);
+        let construct = variant.construct(enum_ident);
+        let payload_pattern = variant.payload_pattern();
+        let payload_expr = variant.payload_expr();
+        quote::quote! {
+            #pattern => self
+                .#field_name
+                .replace(#payload_expr)
+                .map(|#payload_pattern| #construct),
+        }
+    });
+
+    // The skipped variants have no field, so their values are returned back
+    let insert_method = match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            /// Stores the data of the given value in the field of its variant and returns
+            /// the previously stored value of the same variant, if any, or returns the given value back
+            /// if its variant is skipped.
+            #vis fn insert(
+                &mut self,
+                value: #enum_ty,
+            ) -> ::core::result::Result<::core::option::Option<#enum_ty>, #enum_ty> {
+                ::core::result::Result::Ok(match value {
+                    #(#insert_arms)*
+                    value @ (#skipped_pattern) => return ::core::result::Result::Err(value),
+                })
+            }
+        },
+        None => quote::quote! {
+            /// Stores the data of the given value in the field of its variant and returns
+            /// the previously stored value of the same variant, if any.
+            #vis fn insert(&mut self, value: #enum_ty) -> ::core::option::Option<#enum_ty> {
+                match value {
+                    #(#insert_arms)*
+                }
+            }
+        },
+    };
+
+    // `None` if the value of some field can't be constructed, e.g. for `Vec<_>` without `field_wrap`
+    let field_values: Option<Vec<proc_macro2::TokenStream>> = variants
+        .iter()
+        .map(|variant| {
+            let field_name = &variant.field_meta.field_name;
+            if variant.fields.is_empty()
+                && variant.field_meta.variant_ty.is_none()
+                && !variant.field_meta.field_ty_overridden
+                && variant.field_meta.unwrapped_field_ty.is_none()
+            {
+                // Both the payload and the field are `()`
+                return Some(quote::quote!(#field_name));
+            }
+            let payload_pattern = variant.payload_pattern();
+            let field_value_expr = variant.field_value_expr()?;
+            Some(quote::quote! {
+                #field_name: {
+                    let #payload_pattern = #field_name;
+                    #field_value_expr
+                }
+            })
+        })
+        .collect();
+
+    let try_into_complete_method = field_values.map(|field_values| {
+        quote::quote! {
+            #[doc = concat!(" Returns the [`", stringify!(#variants_data_struct_name), "`] if the data of every variant is present")]
+            #[doc = concat!(" or the [`", stringify!(#missing_variants_name), "`] error otherwise.")]
+            #vis fn try_into_complete(
+                self,
+            ) -> ::core::result::Result<#variants_data_struct_name #ty_generics, #missing_variants_name> {
+                let Self { #(#field_names,)* .. } = self;
+                #[allow(unreachable_patterns)]
+                match (#(#field_names,)*) {
+                    (#(::core::option::Option::Some(#field_names),)*) => {
+                        ::core::result::Result::Ok(#variants_data_struct_name {
+                            #(#field_values,)*
+                            #data_marker_init
+                        })
+                    }
+                    (#(#field_names,)*) => {
+                        let is_missing: [(bool, &'static str); #count] =
+                            [#((#field_names.is_none(), #variant_names),)*];
+                        ::core::result::Result::Err(#missing_variants_name {
+                            variants: is_missing
+                                .into_iter()
+                                .filter_map(|(is_missing, name)| is_missing.then_some(name))
+                                .collect(),
+                        })
+                    }
+                }
+            }
+        }
+    });
+
+    let iter_param = fresh_type_param(generics, "I");
+    let doc = format!(" The data of the last inserted value of each variant of [`{enum_ident}`].");
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #where_clause {
+                #(#fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::default::Default for #name #ty_generics #where_clause {
+                fn default() -> Self {
+                    Self {
+                        #(#field_names: ::core::option::Option::None,)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #insert_method
+
+                #try_into_complete_method
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::iter::Extend<#enum_ty> for #name #ty_generics #where_clause {
+                fn extend<#iter_param: ::core::iter::IntoIterator<Item = #enum_ty>>(&mut self, iter: #iter_param) {
+                    // The values of the skipped variants, if any, are dropped
+                    for value in iter {
+                        let _ = self.insert(value);
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::iter::FromIterator<#enum_ty> for #name #ty_generics #where_clause {
+                fn from_iter<#iter_param: ::core::iter::IntoIterator<Item = #enum_ty>>(iter: #iter_param) -> Self {
+                    let mut options = Self::default();
+                    options.extend(iter);
+                    options
+                }
+            }
+        },
+    ]
+}
@@ -0,0 +1,384 @@
+use crate::variants_data_struct_field_attr_meta::{
+    VariantsDataStructFieldAttrMeta, inner_field_default_from_attrs,
+};
+use crate::variants_data_struct_field_meta::VariantsDataStructFieldMeta;
+use crate::variants_data_struct_meta::VariantsDataStructMeta;
+
+/// The original enum, for which the variants data struct is derived.
+pub(crate) struct OriginalEnum {
+    /// The name of the original enum.
+    pub(crate) ident: syn::Ident,
+    /// The generics of the original enum.
+    pub(crate) generics: syn::Generics,
+    /// The primitive representation of the original enum, e.g. `u8` for `#[repr(u8)]`, if specified.
+    pub(crate) repr: Option<syn::Ident>,
+    /// The variants of the original enum that aren't skipped.
+    pub(crate) variants: Vec<OriginalVariant>,
+    /// The names of the skipped variants of the original enum, which have no field in the data struct.
+    pub(crate) skipped_variants: Vec<syn::Ident>,
+    /// Whether some variant of the original enum, skipped or not, has an explicit discriminant.
+    ///
+    /// Unlike the discriminants of the variants, it isn't affected by the implicit discriminants that are made
+    /// explicit after the skipped variants.
+    pub(crate) explicit_discriminants: bool,
+}
+
+/// A variant of the original enum together with the resolved metadata for its field in the data struct
+/// and its "variant type".
+pub(crate) struct OriginalVariant {
+    /// The name of the variant.
+    pub(crate) ident: syn::Ident,
+    /// The fields of the variant.
+    pub(crate) fields: syn::Fields,
+    /// The explicit discriminant of the variant, e.g. `3` for `A = 3`, if specified.
+    pub(crate) discriminant: Option<syn::Expr>,
+    /// The default expression of each field of the variant, e.g. `42` for
+    /// `#[variants_data_struct_field(default = 42)] u32`, if specified.
+    pub(crate) field_defaults: Vec<Option<syn::Expr>>,
+    /// The resolved metadata for the field in the data struct and the "variant type".
+    pub(crate) field_meta: VariantsDataStructFieldMeta,
+}
+
+impl OriginalEnum {
+    pub(crate) fn resolve(
+        attrs: &[syn::Attribute],
+        ident: syn::Ident,
+        generics: syn::Generics,
+        variants: syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
+        variants_data_struct_meta: &VariantsDataStructMeta,
+    ) -> syn::Result<OriginalEnum> {
+        let repr = repr_from_attrs(attrs)?;
+
+        let VariantsDataStructMeta {
+            exclude_units,
+            only,
+            except,
+            ..
+        } = variants_data_struct_meta;
+
+        // The variants of the filters must be those of the original enum
+        for filtered_ident in only.iter().flatten().chain(except) {
+            if !variants
+                .iter()
+                .any(|variant| &variant.ident == filtered_ident)
+            {
+                return Err(syn::Error::new_spanned(
+                    filtered_ident,
+                    "unknown variant in the filters of variants_data_struct attribute",
+                ));
+            }
+        }
+
+        let explicit_discriminants = variants
+            .iter()
+            .any(|variant| variant.discriminant.is_some());
+
+        let mut skipped_variants: Vec<syn::Ident> = vec![];
+        // The implicit discriminants of the variants after a skipped variant are made explicit,
+        // so that the kind enum and the reflection metadata keep the discriminants of the original enum
+        let mut explicit_discriminant: Option<syn::Expr> = None;
+        let mut offset: usize = 0;
+        let mut skipped_since_explicit_discriminant: bool = false;
+
+        let variants = variants
+            .into_iter()
+            .filter_map(|mut variant| {
+                if let Some((_eq_token, discriminant)) = &variant.discriminant {
+                    explicit_discriminant = Some(discriminant.clone());
+                    offset = 0;
+                    skipped_since_explicit_discriminant = false;
+                }
+                let implicit_discriminant: syn::Expr = {
+                    let offset = proc_macro2::Literal::usize_unsuffixed(offset);
+                    match &explicit_discriminant {
+                        Some(explicit_discriminant) => {
+                            syn::parse_quote!((#explicit_discriminant) + #offset)
+                        }
+                        None => syn::parse_quote!(#offset),
+                    }
+                };
+                offset += 1;
+
+                // Parse the `variants_data_struct_field` attribute meta for the variant
+                let variants_data_struct_field_attr_meta: VariantsDataStructFieldAttrMeta =
+                    match VariantsDataStructFieldAttrMeta::from_attrs(&variant.attrs) {
+                        Ok(attr_meta) => attr_meta.unwrap_or_default(),
+                        Err(err) => return Some(Err(err)),
+                    };
+
+                let skip = variants_data_struct_field_attr_meta.skip
+                    || (*exclude_units && matches!(variant.fields, syn::Fields::Unit))
+                    || only
+                        .as_ref()
+                        .is_some_and(|only| !only.contains(&variant.ident))
+                    || except.contains(&variant.ident);
+                if skip {
+                    skipped_variants.push(variant.ident);
+                    skipped_since_explicit_discriminant = true;
+                    return None;
+                }
+
+                if variant.discriminant.is_none() && skipped_since_explicit_discriminant {
+                    variant.discriminant = Some((Default::default(), implicit_discriminant));
+                }
+
+                Some(Self::resolve_variant(
+                    variant,
+                    variants_data_struct_field_attr_meta,
+                    variants_data_struct_meta,
+                    &generics,
+                ))
+            })
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        Ok(OriginalEnum {
+            ident,
+            generics,
+            repr,
+            variants,
+            skipped_variants,
+            explicit_discriminants,
+        })
+    }
+
+    /// Resolves the variant of the original enum that isn't skipped.
+    fn resolve_variant(
+        variant: syn::Variant,
+        variants_data_struct_field_attr_meta: VariantsDataStructFieldAttrMeta,
+        variants_data_struct_meta: &VariantsDataStructMeta,
+        generics: &syn::Generics,
+    ) -> syn::Result<OriginalVariant> {
+        let deref = variants_data_struct_field_attr_meta.deref;
+
+        // Resolve the final metadata for the variant field and the variant type
+        let field_meta = VariantsDataStructFieldMeta::resolve(
+            variants_data_struct_field_attr_meta,
+            variants_data_struct_meta,
+            generics,
+            &variant,
+        )?;
+
+        // The `Deref` implementations need the variant type with a single field
+        if deref && (field_meta.variant_ty.is_none() || variant.fields.len() != 1) {
+            return Err(syn::Error::new_spanned(
+                &variant.ident,
+                "deref of variant_field attribute requires a variant type with a single field",
+            ));
+        }
+
+        // Parse the `variants_data_struct_field` attribute meta for each field of the variant
+        let field_defaults = variant
+            .fields
+            .iter()
+            .map(|field| inner_field_default_from_attrs(&field.attrs))
+            .collect::<syn::Result<Vec<_>>>()?;
+
+        let syn::Variant {
+            ident,
+            fields,
+            discriminant,
+            ..
+        } = variant;
+
+        Ok(OriginalVariant {
+            ident,
+            fields,
+            discriminant: discriminant.map(|(_eq_token, discriminant)| discriminant),
+            field_defaults,
+            field_meta,
+        })
+    }
+
+    /// Returns the type of the original enum with its generic parameters applied, e.g. `MyEnum<T>`.
+    pub(crate) fn ty(&self) -> syn::Type {
+        let ident = &self.ident;
+        let (_impl_generics, ty_generics, _where_clause) = self.generics.split_for_impl();
+        syn::parse_quote!(#ident #ty_generics)
+    }
+
+    /// Returns the pattern matching the skipped variants of the original enum, e.g.
+    /// `MyEnum::Unknown { .. } | MyEnum::Other { .. }`, if there are any.
+    pub(crate) fn skipped_pattern(&self) -> Option<syn::Pat> {
+        let ident = &self.ident;
+        let skipped_variants = &self.skipped_variants;
+        if skipped_variants.is_empty() {
+            return None;
+        }
+        Some(syn::parse_quote!(#(#ident::#skipped_variants { .. })|*))
+    }
+}
+
+/// Returns the primitive representation of the enum from its `#[repr(...)]` attributes, if specified.
+fn repr_from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Option<syn::Ident>> {
+    const PRIMITIVE_REPRS: [&str; 12] = [
+        "u8", "u16", "u32", "u64", "u128", "usize", "i8", "i16", "i32", "i64", "i128", "isize",
+    ];
+
+    let mut repr: Option<syn::Ident> = None;
+    for attr in attrs.iter().filter(|attr| attr.path().is_ident("repr")) {
+        attr.parse_nested_meta(|meta| {
+            if let Some(ident) = meta.path.get_ident()
+                && PRIMITIVE_REPRS.contains(&ident.to_string().as_str())
+            {
+                repr = Some(ident.clone());
+            } else if meta.input.peek(syn::token::Paren) {
+                // Skip the arguments of e.g. `align(8)`
+                let content;
+                let _paren_token = syn::parenthesized!(content in meta.input);
+                let _: proc_macro2::TokenStream = content.parse()?;
+            }
+            Ok(())
+        })?;
+    }
+    Ok(repr)
+}
+
+impl OriginalVariant {
+    /// Returns the members of the fields of the variant, i.e. their names or indices.
+    pub(crate) fn members(&self) -> Vec<syn::Member> {
+        self.fields.members().collect()
+    }
+
+    /// Returns the names of the local variables the fields of the variant are bound to,
+    /// i.e. the names of the named fields and `field_<index>` for unnamed fields.
+    pub(crate) fn bindings(&self) -> Vec<syn::Ident> {
+        self.fields
+            .members()
+            .map(|member| match member {
+                syn::Member::Named(ident) => ident,
+                syn::Member::Unnamed(index) => quote::format_ident!("field_{}", index.index),
+            })
+            .collect()
+    }
+
+    /// Returns the field patterns or initializers that bind the fields of the variant to
+    /// (or initialize them from) the local variables returned by [`OriginalVariant::bindings`],
+    /// e.g. `id` for named fields and `0: field_0` for unnamed fields.
+    pub(crate) fn bound_fields(&self) -> Vec<proc_macro2::TokenStream> {
+        self.members()
+            .into_iter()
+            .zip(self.bindings())
+            .map(|(member, binding)| match member {
+                syn::Member::Named(_) => quote::quote!(#binding),
+                syn::Member::Unnamed(_) => quote::quote!(#member: #binding),
+            })
+            .collect()
+    }
+
+    /// Returns the pattern that matches the variant of the original enum and binds its fields
+    /// (see [`OriginalVariant::bindings`]), e.g. `MyEnum::TupleEnum { 0: field_0, 1: field_1 }`.
+    pub(crate) fn pattern(&self, enum_ident: &syn::Ident) -> syn::Pat {
+        let ident = &self.ident;
+        let bound_fields = self.bound_fields();
+        syn::parse_quote!(#enum_ident::#ident { #(#bound_fields),* })
+    }
+
+    /// Returns the expression that constructs the variant of the original enum from the bound fields
+    /// (see [`OriginalVariant::bindings`]), e.g. `MyEnum::TupleEnum { 0: field_0, 1: field_1 }`.
+    pub(crate) fn construct(&self, enum_ident: &syn::Ident) -> syn::Expr {
+        let ident = &self.ident;
+        let bound_fields = self.bound_fields();
+        syn::parse_quote!(#enum_ident::#ident { #(#bound_fields),* })
+    }
+
+    /// Returns the type of the "payload" of the variant (see [`OriginalVariant::payload_pattern`]),
+    /// e.g. `TupleEnumVariantType` or `(i32, String)`.
+    pub(crate) fn payload_ty(&self) -> syn::Type {
+        if let Some(variant_ty) = &self.field_meta.variant_ty {
+            return variant_ty.ty();
+        }
+        let tys: Vec<&syn::Type> = self.fields.iter().map(|field| &field.ty).collect();
+        match tys.as_slice() {
+            [ty] => (*ty).clone(),
+            _ => syn::parse_quote!((#(#tys,)*)),
+        }
+    }
+
+    /// Returns the pattern that destructures the "payload" of the variant and binds its fields
+    /// (see [`OriginalVariant::bindings`]).
+    ///
+    /// The "payload" of the variant is the data of the variant as a single value:
+    ///
+    /// * the "variant type", if it is generated,
+    /// * `()` for variants without fields,
+    /// * the value of the only field for variants with a single field,
+    /// * the tuple of the values of the fields otherwise.
+    pub(crate) fn payload_pattern(&self) -> syn::Pat {
+        let bindings = self.bindings();
+        if let Some(variant_ty) = &self.field_meta.variant_ty {
+            let name = &variant_ty.name;
+            let bound_fields = self.bound_fields();
+            return syn::parse_quote!(#name { #(#bound_fields,)* .. });
+        }
+        match bindings.as_slice() {
+            [binding] => syn::parse_quote!(#binding),
+            _ => syn::parse_quote!((#(#bindings,)*)),
+        }
+    }
+
+    /// Returns the expression that constructs the "payload" of the variant (see [`OriginalVariant::payload_pattern`])
+    /// from the bound fields (see [`OriginalVariant::bindings`]).
+    pub(crate) fn payload_expr(&self) -> syn::Expr {
+        let bindings = self.bindings();
+        if let Some(variant_ty) = &self.field_meta.variant_ty {
+            let name = &variant_ty.name;
+            let bound_fields = self.bound_fields();
+            let marker = variant_ty
+                .marker_member(&self.fields)
+                .map(|marker_member| quote::quote!(#marker_member: ::core::marker::PhantomData,));
+            return syn::parse_quote!(#name { #(#bound_fields,)* #marker });
+        }
+        match bindings.as_slice() {
+            [binding] => syn::parse_quote!(#binding),
+            _ => syn::parse_quote!((#(#bindings,)*)),
+        }
+    }
+
+    /// Returns the expression that constructs the value of the field in the data struct
+    /// from the bound fields (see [`OriginalVariant::bindings`]), i.e.
+    ///
+    /// * the "variant type", if it is generated,
+    /// * the conversion of the "payload" (see [`OriginalVariant::payload_pattern`]) with the `field_from` function,
+    ///   if the type of the field is overridden,
+    /// * the tuple of the values of the fields otherwise,
+    ///
+    /// wrapped with the `field_wrap` function, if the field is wrapped (e.g. with `Option<_>`).
+    ///
+    /// Returns `None` if the type of the field is overridden without `field_from` or if the field is wrapped but
+    /// the function that wraps its value is unknown, e.g. for `Vec<_>` without `field_wrap`.
+    pub(crate) fn field_value_expr(&self) -> Option<syn::Expr> {
+        let unwrapped_field_value_expr: syn::Expr = if self.field_meta.field_ty_overridden {
+            let field_from = self.field_meta.field_from.as_ref()?;
+            let payload_expr = self.payload_expr();
+            syn::parse_quote!((#field_from)(#payload_expr))
+        } else if self.field_meta.variant_ty.is_some() {
+            self.payload_expr()
+        } else {
+            let bindings = self.bindings();
+            syn::parse_quote!((#(#bindings,)*))
+        };
+        match self.field_meta.unwrapped_field_ty {
+            Some(_) => {
+                let field_wrap = self.field_meta.field_wrap.as_ref()?;
+                Some(syn::parse_quote!((#field_wrap)(#unwrapped_field_value_expr)))
+            }
+            None => Some(unwrapped_field_value_expr),
+        }
+    }
+
+    /// Returns the pattern that destructures the value of the field in the data struct and binds the fields
+    /// of the variant (see [`OriginalVariant::bindings`]), i.e. the inverse of [`OriginalVariant::field_value_expr`].
+    ///
+    /// Returns `None` if the type of the field is overridden or the field is wrapped (e.g. with `Option<_>`),
+    /// since such values can't be converted back into the variant.
+    pub(crate) fn field_value_pattern(&self) -> Option<syn::Pat> {
+        if self.field_meta.field_ty_overridden || self.field_meta.unwrapped_field_ty.is_some() {
+            return None;
+        }
+        if self.field_meta.variant_ty.is_some() {
+            return Some(self.payload_pattern());
+        }
+        let bindings = self.bindings();
+        Some(syn::parse_quote!((#(#bindings,)*)))
+    }
+}
@@ -0,0 +1,242 @@
+use crate::generics::{UsedGenericParams, fresh_type_param, generic_args, phantom_marker_ident};
+use crate::original_enum::OriginalEnum;
+use crate::variants_data_struct_defs::variants_data_struct_marker_ty;
+
+/// The meta of the `record(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`RecordMeta`].
+#[derive(Default)]
+pub(crate) struct RecordAttrMeta {
+    /// The override for the name of the record struct.
+    ///
+    /// If not provided, the default name is `<EnumName>VariantsRecord`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the record struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for RecordAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in record of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(RecordAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the record struct of the original enum.
+///
+/// For the raw attribute meta, see [`RecordAttrMeta`].
+pub(crate) struct RecordMeta {
+    /// The name of the record struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the record struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl RecordMeta {
+    pub(crate) fn resolve(attr_meta: RecordAttrMeta, enum_ident: &syn::Ident) -> RecordMeta {
+        let RecordAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}VariantsRecord"), enum_ident.span()),
+        };
+
+        RecordMeta { name, attrs }
+    }
+}
+
+/// Generates the record struct of the original enum, i.e.
+///
+/// * the `MyEnumVariantsRecord<UnitEnum = (), TupleEnum = TupleEnumVariantType, ...>` struct with
+///   a type parameter for each field of the derived variants data struct, which defaults to the type of the field,
+/// * its `map_<field_name>` methods, which change the type of the respective field,
+/// * the conversions between the record struct with the default type parameters and the derived variants data struct.
+pub(crate) fn record(
+    // The resolved metadata for the record struct.
+    record_meta: &RecordMeta,
+    // The visibility of the record struct and its fields, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct, which precede the type parameters of the fields.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let RecordMeta { name, attrs } = record_meta;
+
+    let enum_ident = &original_enum.ident;
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let field_tys: Vec<&syn::Type> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_ty)
+        .collect();
+
+    // The type parameters of the fields are named after the variants, e.g. `TupleEnum`
+    let mut record_generics = generics.clone();
+    let mut field_params: Vec<syn::Ident> = vec![];
+    for (variant, field_ty) in variants.iter().zip(&field_tys) {
+        let field_param = fresh_type_param(&record_generics, &variant.ident.to_string());
+        record_generics
+            .params
+            .push(syn::parse_quote!(#field_param = #field_ty));
+        field_params.push(field_param);
+    }
+    if !record_generics.params.is_empty() {
+        record_generics
+            .lt_token
+            .get_or_insert_with(Default::default);
+        record_generics
+            .gt_token
+            .get_or_insert_with(Default::default);
+    }
+    let (record_impl_generics, record_ty_generics, record_where_clause) =
+        record_generics.split_for_impl();
+    let record_params = &record_generics.params;
+
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let data_args = generic_args(generics);
+    let default_record_ty: syn::Type = syn::parse_quote!(#name<#(#data_args,)* #(#field_tys,)*>);
+    let variants_data_struct_ty: syn::Type =
+        syn::parse_quote!(#variants_data_struct_name #ty_generics);
+
+    // None of the generic parameters of the derived variants data struct is used by the fields of the record struct
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match UsedGenericParams::new(generics).phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+    let data_marker_init = variants_data_struct_marker_ty(generics, variants)
+        .map(|_marker_ty| quote::quote!(#marker_ident: ::core::marker::PhantomData,));
+
+    let fields = variants
+        .iter()
+        .zip(&field_params)
+        .map(|(variant, field_param)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(" The data of [`{enum_ident}::{}`].", variant.ident);
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: #field_param
+            }
+        });
+
+    let mapped_param = fresh_type_param(&record_generics, "U");
+    let map_methods = variants.iter().enumerate().map(|(i, variant)| {
+        let field_name = &variant.field_meta.field_name;
+        let field_param = &field_params[i];
+        let method_name = quote::format_ident!("map_{}", field_name);
+        let mapped_args = field_params.iter().enumerate().map(|(j, field_param)| {
+            if i == j {
+                &mapped_param
+            } else {
+                field_param
+            }
+        });
+        let other_field_names = field_names
+            .iter()
+            .enumerate()
+            .filter(|(j, _field_name)| i != *j)
+            .map(|(_j, field_name)| field_name);
+        let doc = format!(
+            " Maps the data of [`{enum_ident}::{}`] with the function, keeping the data of the other variants.",
+            variant.ident
+        );
+        quote::quote! {
+            #[doc = #doc]
+            #vis fn #method_name<#mapped_param>(
+                self,
+                f: impl ::core::ops::FnOnce(#field_param) -> #mapped_param,
+            ) -> #name<#(#data_args,)* #(#mapped_args,)*> {
+                #name {
+                    #field_name: f(self.#field_name),
+                    #(#other_field_names: self.#other_field_names,)*
+                    #marker_init
+                }
+            }
+        }
+    });
+
+    let doc = format!(
+        " The data of each variant of [`{enum_ident}`] with a separate type parameter for each variant."
+    );
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#record_params> #record_where_clause {
+                #(#fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #record_impl_generics #name #record_ty_generics #record_where_clause {
+                #(#map_methods)*
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::convert::From<#variants_data_struct_ty> for #default_record_ty #where_clause {
+                fn from(data: #variants_data_struct_ty) -> Self {
+                    #name {
+                        #(#field_names: data.#field_names,)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::convert::From<#default_record_ty> for #variants_data_struct_ty #where_clause {
+                fn from(record: #default_record_ty) -> Self {
+                    #variants_data_struct_name {
+                        #(#field_names: record.#field_names,)*
+                        #data_marker_init
+                    }
+                }
+            }
+        },
+    ]
+}
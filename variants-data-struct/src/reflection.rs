@@ -0,0 +1,118 @@
+use crate::original_enum::OriginalEnum;
+
+/// Generates the reflection metadata of the original enum, i.e.
+///
+/// * the `VARIANTS` constant of the original enum with the `VariantInfo` of each variant,
+/// * the `field_names` and `get_by_name` methods of the derived variants data struct.
+pub(crate) fn reflection(
+    // The visibility of the constant and the methods, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+    // The path to the `variants-data-struct-runtime` crate.
+    runtime: &syn::Path,
+) -> Vec<syn::Item> {
+    let enum_ident = &original_enum.ident;
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) =
+        original_enum.generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    let field_names: Vec<&syn::Ident> = original_enum
+        .variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let field_name_strs: Vec<String> = field_names
+        .iter()
+        .map(|field_name| field_name.to_string())
+        .collect();
+
+    // The implicit discriminants are the previous explicit discriminant plus the offset from it,
+    // or the index of the variant if there is no previous explicit discriminant
+    let mut explicit_discriminant: Option<&syn::Expr> = None;
+    let mut offset: usize = 0;
+    let variant_infos = original_enum.variants.iter().map(|variant| {
+        let name = variant.ident.to_string();
+        let field_name = variant.field_meta.field_name.to_string();
+        let variant_ty_name = match &variant.field_meta.variant_ty {
+            Some(variant_ty) => {
+                let variant_ty_name = variant_ty.name.to_string();
+                quote::quote!(::core::option::Option::Some(#variant_ty_name))
+            }
+            None => quote::quote!(::core::option::Option::None),
+        };
+        let shape = match &variant.fields {
+            syn::Fields::Unit => quote::quote!(#runtime::VariantShape::Unit),
+            syn::Fields::Unnamed(_) => quote::quote!(#runtime::VariantShape::Tuple),
+            syn::Fields::Named(_) => quote::quote!(#runtime::VariantShape::Struct),
+        };
+        let fields = variant.fields.iter().enumerate().map(|(i, field)| {
+            let name = match &field.ident {
+                Some(ident) => ident.to_string(),
+                None => i.to_string(),
+            };
+            let ty = &field.ty;
+            quote::quote! {
+                #runtime::FieldInfo {
+                    name: #name,
+                    type_name_fn: ::core::any::type_name::<#ty>,
+                }
+            }
+        });
+
+        if let Some(discriminant) = &variant.discriminant {
+            explicit_discriminant = Some(discriminant);
+            offset = 0;
+        }
+        let discriminant = match explicit_discriminant {
+            Some(explicit_discriminant) => {
+                quote::quote!((#explicit_discriminant) as i128 + #offset as i128)
+            }
+            None => quote::quote!(#offset as i128),
+        };
+        offset += 1;
+
+        quote::quote! {
+            #runtime::VariantInfo {
+                name: #name,
+                field_name: #field_name,
+                variant_ty_name: #variant_ty_name,
+                shape: #shape,
+                fields: &[#(#fields,)*],
+                discriminant: #discriminant,
+            }
+        }
+    });
+
+    vec![
+        syn::parse_quote! {
+            impl #enum_impl_generics #enum_ident #enum_ty_generics #enum_where_clause {
+                /// The metadata of the variants in the order of their declaration.
+                #vis const VARIANTS: &'static [#runtime::VariantInfo] = &[#(#variant_infos,)*];
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #variants_data_struct_name #ty_generics #where_clause {
+                /// Returns the names of the fields in the order of the variants.
+                #vis fn field_names() -> &'static [&'static str] {
+                    &[#(#field_name_strs,)*]
+                }
+
+                /// Returns the reference to the field with the given name, if there is one.
+                #vis fn get_by_name(&self, name: &str) -> ::core::option::Option<&dyn ::core::any::Any>
+                where
+                    Self: 'static,
+                {
+                    match name {
+                        #(#field_name_strs => ::core::option::Option::Some(&self.#field_names),)*
+                        _ => ::core::option::Option::None,
+                    }
+                }
+            }
+        },
+    ]
+}
@@ -0,0 +1,290 @@
+use crate::generics::{UsedGenericParams, phantom_marker_ident};
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `router(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`RouterMeta`].
+#[derive(Default)]
+pub(crate) struct RouterAttrMeta {
+    /// The override for the name of the router struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Router`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The override for the name of the receivers struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Receivers`.
+    pub(crate) receivers_name: Option<syn::Ident>,
+    /// The attributes to be applied to the router and the receivers structs.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for RouterAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut receivers_name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "receivers_name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let receivers_name_ident: syn::Ident = input.parse()?;
+                    receivers_name = Some(receivers_name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in router of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(RouterAttrMeta {
+            name,
+            receivers_name,
+            attrs,
+        })
+    }
+}
+
+/// The resolved metadata for the router of the original enum.
+///
+/// For the raw attribute meta, see [`RouterAttrMeta`].
+pub(crate) struct RouterMeta {
+    /// The name of the router struct.
+    pub(crate) name: syn::Ident,
+    /// The name of the receivers struct.
+    pub(crate) receivers_name: syn::Ident,
+    /// The attributes to be applied to the router and the receivers structs.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl RouterMeta {
+    pub(crate) fn resolve(attr_meta: RouterAttrMeta, enum_ident: &syn::Ident) -> RouterMeta {
+        let RouterAttrMeta {
+            name,
+            receivers_name,
+            attrs,
+        } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Router"), enum_ident.span()),
+        };
+
+        let receivers_name = match receivers_name {
+            Some(receivers_name) => receivers_name,
+            None => syn::Ident::new(&format!("{enum_ident}Receivers"), enum_ident.span()),
+        };
+
+        RouterMeta {
+            name,
+            receivers_name,
+            attrs,
+        }
+    }
+}
+
+/// Generates the router of the original enum, i.e.
+///
+/// * the `MyEnumRouter` struct with a `Sender` of the "payloads" of each variant
+///   (see [`OriginalVariant::payload_pattern`](crate::original_enum::OriginalVariant::payload_pattern)),
+/// * the `MyEnumReceivers` struct with the matching `Receiver` of each variant,
+/// * the `channel` function of the router, which creates both, its `route` method and its `Clone` implementation.
+pub(crate) fn router(
+    // The resolved metadata for the router.
+    router_meta: &RouterMeta,
+    // The visibility of the router and the receivers structs, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+    // The path to the `variants-data-struct-runtime` crate.
+    runtime: &syn::Path,
+) -> Vec<syn::Item> {
+    let RouterMeta {
+        name,
+        receivers_name,
+        attrs,
+    } = router_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let generics = &original_enum.generics;
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let payload_tys: Vec<syn::Type> = variants
+        .iter()
+        .map(|variant| variant.payload_ty())
+        .collect();
+
+    // Mark the generic parameters of the original enum that no payload uses
+    let mut used_generic_params = UsedGenericParams::new(generics);
+    payload_tys
+        .iter()
+        .for_each(|payload_ty| used_generic_params.visit_type(payload_ty));
+    let marker_ident = phantom_marker_ident();
+    let (marker_field, marker_init) = match used_generic_params.phantom_marker_ty() {
+        Some(marker_ty) => (
+            Some(quote::quote!(#[doc(hidden)] #vis #marker_ident: #marker_ty,)),
+            Some(quote::quote!(#marker_ident: ::core::marker::PhantomData,)),
+        ),
+        None => (None, None),
+    };
+
+    let sender_fields = variants
+        .iter()
+        .zip(&payload_tys)
+        .map(|(variant, payload_ty)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(
+                " The sender of the data of [`{enum_ident}::{}`].",
+                variant.ident
+            );
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: ::std::sync::mpsc::Sender<#payload_ty>
+            }
+        });
+
+    let receiver_fields = variants
+        .iter()
+        .zip(&payload_tys)
+        .map(|(variant, payload_ty)| {
+            let field_name = &variant.field_meta.field_name;
+            let doc = format!(
+                " The receiver of the data of [`{enum_ident}::{}`].",
+                variant.ident
+            );
+            quote::quote! {
+                #[doc = #doc]
+                #vis #field_name: ::std::sync::mpsc::Receiver<#payload_ty>
+            }
+        });
+
+    let route_arms = variants.iter().map(|variant| {
+        let field_name = &variant.field_meta.field_name;
+        let variant_name = variant.ident.to_string();
+        let pattern = variant.pattern(enum_ident);
+        let construct = variant.construct(enum_ident);
+        let payload_pattern = variant.payload_pattern();
+        let payload_expr = variant.payload_expr();
+        quote::quote! {
+            #pattern => self.#field_name.send(#payload_expr).map_err(|error| {
+                let #payload_pattern = error.0;
+                #runtime::RouteError {
+                    value: #construct,
+                    variant: #variant_name,
+                    reason: #runtime::RouteErrorReason::Disconnected,
+                }
+            }),
+        }
+    });
+
+    // The skipped variants have no channel, so routing their values fails as if the receiver was disconnected
+    let skipped_arms = original_enum
+        .skipped_variants
+        .iter()
+        .map(|skipped_variant| {
+            let variant_name = skipped_variant.to_string();
+            quote::quote! {
+                value @ #enum_ident::#skipped_variant { .. } => {
+                    ::core::result::Result::Err(#runtime::RouteError {
+                        value,
+                        variant: #variant_name,
+                        reason: #runtime::RouteErrorReason::Skipped,
+                    })
+                }
+            }
+        });
+
+    let doc = format!(
+        " The senders of the data of each variant of [`{enum_ident}`], which route the values to the matching receivers."
+    );
+    let receivers_doc = format!(" The receivers of the data of each variant of [`{enum_ident}`].");
+
+    vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #where_clause {
+                #(#sender_fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            #[doc = #receivers_doc]
+            #(#attrs)*
+            #vis struct #receivers_name<#params> #where_clause {
+                #(#receiver_fields,)*
+                #marker_field
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #name #ty_generics #where_clause {
+                #[doc = concat!(" Creates a channel for each variant and returns the router and the [`", stringify!(#receivers_name), "`].")]
+                #vis fn channel() -> (Self, #receivers_name #ty_generics) {
+                    #(let #field_names = ::std::sync::mpsc::channel();)*
+                    let router = Self {
+                        #(#field_names: #field_names.0,)*
+                        #marker_init
+                    };
+                    let receivers = #receivers_name {
+                        #(#field_names: #field_names.1,)*
+                        #marker_init
+                    };
+                    (router, receivers)
+                }
+
+                /// Sends the data of the value to the receiver of its variant.
+                ///
+                /// Returns the value back in the error if the receiver is disconnected or the variant is skipped.
+                #vis fn route(&self, value: #enum_ty) -> ::core::result::Result<(), #runtime::RouteError<#enum_ty>> {
+                    match value {
+                        #(#route_arms)*
+                        #(#skipped_arms)*
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::clone::Clone for #name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    Self {
+                        #(#field_names: ::core::clone::Clone::clone(&self.#field_names),)*
+                        #marker_init
+                    }
+                }
+            }
+        },
+    ]
+}
@@ -0,0 +1,82 @@
+use crate::generics::merge_generics;
+use crate::kind::KindMeta;
+use crate::original_enum::OriginalEnum;
+
+/// Generates the implementations of the traits of the `variants-data-struct-runtime` crate, i.e.
+///
+/// * `impl HasVariantsData for MyEnum` with the derived variants data struct and the kind enum
+///   (or the `Option` of it if some variants are skipped), and
+/// * `impl VariantOf<MyEnum> for VariantType` for each "variant type".
+pub(crate) fn traits(
+    // The resolved metadata for the kind enum, which is the `Kind` of the original enum.
+    kind_meta: &KindMeta,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+    // The path to the `variants-data-struct-runtime` crate.
+    runtime: &syn::Path,
+) -> syn::Result<Vec<syn::Item>> {
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let kind_name = &kind_meta.name;
+    let count = original_enum.variants.len();
+
+    // The data struct is the associated type of the original enum, so it can't have additional generic parameters
+    if generics.params.len() != original_enum.generics.params.len() {
+        return Err(syn::Error::new_spanned(
+            &generics.params,
+            "traits of variants_data_struct attribute can't be used with additional generic parameters of the data struct",
+        ));
+    }
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+
+    // The skipped variants have no kind, so the kind of a value is optional, like that of the `kind` method
+    let kind_ty: syn::Type = if original_enum.skipped_variants.is_empty() {
+        syn::parse_quote!(#kind_name)
+    } else {
+        syn::parse_quote!(::core::option::Option<#kind_name>)
+    };
+
+    let mut items: Vec<syn::Item> = vec![syn::parse_quote! {
+        impl #impl_generics #runtime::HasVariantsData for #enum_ty #where_clause {
+            type Data = #variants_data_struct_name #ty_generics;
+            type Kind = #kind_ty;
+            const VARIANT_COUNT: usize = #count;
+
+            fn kind(&self) -> Self::Kind {
+                #enum_ident::kind(self)
+            }
+        }
+    }];
+
+    items.extend(
+        original_enum
+            .variants
+            .iter()
+            .enumerate()
+            .filter_map(|(index, variant)| {
+                let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+                let generics =
+                    merge_generics(original_enum.generics.clone(), variant_ty.generics.clone());
+                let (impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+                let variant_ty_ty = variant_ty.ty();
+                let construct = variant.construct(enum_ident);
+                let payload_pattern = variant.payload_pattern();
+                Some(syn::parse_quote! {
+                    impl #impl_generics #runtime::VariantOf<#enum_ty> for #variant_ty_ty #where_clause {
+                        const INDEX: usize = #index;
+
+                        fn into_enum(self) -> #enum_ty {
+                            let #payload_pattern = self;
+                            #construct
+                        }
+                    }
+                })
+            }),
+    );
+
+    Ok(items)
+}
@@ -0,0 +1,212 @@
+use crate::generics::fresh_type_param;
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `uniform(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`UniformMeta`].
+#[derive(Default)]
+pub(crate) struct UniformAttrMeta {
+    /// The override for the name of the uniform map struct.
+    ///
+    /// If not provided, the default name is `<EnumName>Map`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The attributes to be applied to the uniform map struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for UniformAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in uniform of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(UniformAttrMeta { name, attrs })
+    }
+}
+
+/// The resolved metadata for the uniform map struct of the original enum.
+///
+/// For the raw attribute meta, see [`UniformAttrMeta`].
+pub(crate) struct UniformMeta {
+    /// The name of the uniform map struct.
+    pub(crate) name: syn::Ident,
+    /// The attributes to be applied to the uniform map struct.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl UniformMeta {
+    pub(crate) fn resolve(attr_meta: UniformAttrMeta, enum_ident: &syn::Ident) -> UniformMeta {
+        let UniformAttrMeta { name, attrs } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Map"), enum_ident.span()),
+        };
+
+        UniformMeta { name, attrs }
+    }
+}
+
+/// Generates the uniform map struct of the original enum, i.e.
+///
+/// * the `MyEnumMap<T>` struct with a field of type `T` for each variant,
+/// * its `Default`, `Index<&MyEnum>` and `IndexMut<&MyEnum>` implementations (unless some variants are skipped),
+/// * its `from_fn`, `map`, `zip` and `iter` methods.
+pub(crate) fn uniform(
+    // The resolved metadata for the uniform map struct.
+    uniform_meta: &UniformMeta,
+    // The visibility of the uniform map struct and its fields, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let UniformMeta { name, attrs } = uniform_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_ty = original_enum.ty();
+    let enum_generics = &original_enum.generics;
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let variant_idents: Vec<&syn::Ident> = variants.iter().map(|variant| &variant.ident).collect();
+    let variant_names: Vec<String> = variant_idents
+        .iter()
+        .map(|ident| ident.to_string())
+        .collect();
+    let count = variants.len();
+
+    let fields = variants.iter().map(|variant| {
+        let field_name = &variant.field_meta.field_name;
+        let doc = format!(" The value for [`{enum_ident}::{}`].", variant.ident);
+        quote::quote! {
+            #[doc = #doc]
+            #vis #field_name: T
+        }
+    });
+
+    // The `Index` implementations are generic over both the generics of the original enum and the value type
+    let value_param = fresh_type_param(enum_generics, "T");
+    let mut index_generics = enum_generics.clone();
+    index_generics.params.push(syn::parse_quote!(#value_param));
+    index_generics.lt_token.get_or_insert_with(Default::default);
+    index_generics.gt_token.get_or_insert_with(Default::default);
+    let (index_impl_generics, _index_ty_generics, index_where_clause) =
+        index_generics.split_for_impl();
+
+    let doc = format!(" A value of type `T` for each variant of [`{enum_ident}`].");
+
+    let mut items: Vec<syn::Item> = vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<T> {
+                #(#fields,)*
+            }
+        },
+        syn::parse_quote! {
+            impl<T: ::core::default::Default> ::core::default::Default for #name<T> {
+                fn default() -> Self {
+                    Self {
+                        #(#field_names: ::core::default::Default::default(),)*
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl<T> #name<T> {
+                /// Creates the map by calling the function with the name of each variant.
+                #vis fn from_fn(mut f: impl ::core::ops::FnMut(&'static str) -> T) -> Self {
+                    Self {
+                        #(#field_names: f(#variant_names),)*
+                    }
+                }
+
+                /// Maps the value for each variant with the function.
+                #vis fn map<U>(self, mut f: impl ::core::ops::FnMut(T) -> U) -> #name<U> {
+                    #name {
+                        #(#field_names: f(self.#field_names),)*
+                    }
+                }
+
+                /// Combines the values for each variant of the two maps into pairs.
+                #vis fn zip<U>(self, other: #name<U>) -> #name<(T, U)> {
+                    #name {
+                        #(#field_names: (self.#field_names, other.#field_names),)*
+                    }
+                }
+
+                /// Returns an iterator over the names of the variants and the references to their values.
+                #vis fn iter(&self) -> impl ::core::iter::Iterator<Item = (&'static str, &T)> {
+                    let entries: [(&'static str, &T); #count] = [#((#variant_names, &self.#field_names),)*];
+                    entries.into_iter()
+                }
+            }
+        },
+    ];
+
+    // The skipped variants have no value in the map, so it can't be indexed by the values of the original enum
+    if original_enum.skipped_pattern().is_some() {
+        return items;
+    }
+
+    items.extend([
+        syn::parse_quote! {
+            impl #index_impl_generics ::core::ops::Index<&#enum_ty> for #name<#value_param> #index_where_clause {
+                type Output = #value_param;
+
+                fn index(&self, value: &#enum_ty) -> &Self::Output {
+                    match *value {
+                        #(#enum_ident::#variant_idents { .. } => &self.#field_names,)*
+                    }
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #index_impl_generics ::core::ops::IndexMut<&#enum_ty> for #name<#value_param> #index_where_clause {
+                fn index_mut(&mut self, value: &#enum_ty) -> &mut Self::Output {
+                    match *value {
+                        #(#enum_ident::#variant_idents { .. } => &mut self.#field_names,)*
+                    }
+                }
+            }
+        },
+    ]);
+
+    items
+}
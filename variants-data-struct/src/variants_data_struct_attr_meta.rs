@@ -1,7 +1,24 @@
+use crate::accessors::AccessorsAttrMeta;
+use crate::builder::BuilderAttrMeta;
+use crate::columns::ColumnsAttrMeta;
+use crate::counters::CountersAttrMeta;
+use crate::family::FamilyAttrMeta;
+use crate::field_wrapper::{enabled_field_wrapper, parse_field_wrapper};
+use crate::handlers::HandlersAttrMeta;
+use crate::kind::KindAttrMeta;
+use crate::markers::MarkersAttrMeta;
+use crate::options::OptionsAttrMeta;
+use crate::record::RecordAttrMeta;
+use crate::router::RouterAttrMeta;
+use crate::uniform::UniformAttrMeta;
+use crate::views::ViewsAttrMeta;
+use crate::visitor::VisitorAttrMeta;
+
 /// The [`syn::Attribute::meta`] for the `variants_data_struct` attribute.
 ///
 /// For the resolved values (e.g., with defaults applied), see
 /// [`VariantsDataStructMeta`](crate::variants_data_struct_meta::VariantsDataStructMeta).
+#[derive(Default)]
 pub(crate) struct VariantsDataStructAttrMeta {
     /// The attributes to be applied to the derived variants data struct.
     pub(crate) attrs: Vec<syn::Attribute>,
@@ -16,23 +33,67 @@ pub(crate) struct VariantsDataStructAttrMeta {
     /// The attributes to be applied to the "variant types",
     /// which are the generated types for the respective original enum's variants.
     pub(crate) variants_tys_attrs: Vec<syn::Attribute>,
-}
-
-impl Default for VariantsDataStructAttrMeta {
-    fn default() -> Self {
-        Self {
-            attrs: vec![],
-            vis: None,
-            name: None,
-            variants_tys_attrs: vec![],
-        }
-    }
+    /// The additional generic parameters and where-clause predicates of the derived variants data struct,
+    /// which are merged with the generics of the original enum.
+    pub(crate) generics: syn::Generics,
+    /// The template for wrapping the types of the fields of the derived variants data struct, e.g. `Option<_>`.
+    pub(crate) field_wrapper: Option<syn::Type>,
+    /// The function or closure that wraps the values of the fields with the field wrapper, e.g. `|value| vec![value]`.
+    pub(crate) field_wrap: Option<syn::Expr>,
+    /// The override for the path to the `variants-data-struct-runtime` crate, e.g. when it is re-exported.
+    ///
+    /// If not provided, the default path is `::variants_data_struct_runtime`.
+    pub(crate) crate_path: Option<syn::Path>,
+    /// Whether to generate the conversions between the original enum and the "variant types".
+    pub(crate) conversions: bool,
+    /// Whether to generate the per-variant methods of the original enum, e.g. `is_tuple_enum`.
+    pub(crate) enum_methods: bool,
+    /// Whether to implement the traits of the `variants-data-struct-runtime` crate, e.g. `HasVariantsData`.
+    pub(crate) traits: bool,
+    /// Whether to generate the reflection metadata of the original enum, e.g. the `VARIANTS` constant.
+    pub(crate) reflection: bool,
+    /// Whether to implement `Default` for the derived variants data struct and the "variant types".
+    pub(crate) default: bool,
+    /// The meta for the accessors of the fields of the derived variants data struct, if they are to be generated.
+    pub(crate) accessors: Option<AccessorsAttrMeta>,
+    /// Whether to skip the unit variants of the original enum.
+    pub(crate) exclude_units: bool,
+    /// The variants of the original enum to keep, if only some of them are to be kept.
+    pub(crate) only: Option<Vec<syn::Ident>>,
+    /// The variants of the original enum to skip.
+    pub(crate) except: Vec<syn::Ident>,
+    /// The meta for the borrowed and mutably borrowed views of the original enum, if they are to be generated.
+    pub(crate) views: Option<ViewsAttrMeta>,
+    /// The meta for the kind enum of the original enum, if it is to be generated.
+    pub(crate) kind: Option<KindAttrMeta>,
+    /// The meta for the options struct of the original enum, if it is to be generated.
+    pub(crate) options: Option<OptionsAttrMeta>,
+    /// The meta for the columns struct of the original enum, if it is to be generated.
+    pub(crate) columns: Option<ColumnsAttrMeta>,
+    /// The meta for the uniform map struct of the original enum, if it is to be generated.
+    pub(crate) uniform: Option<UniformAttrMeta>,
+    /// The meta for the family struct of the original enum, if it is to be generated.
+    pub(crate) family: Option<FamilyAttrMeta>,
+    /// The meta for the record struct of the original enum, if it is to be generated.
+    pub(crate) record: Option<RecordAttrMeta>,
+    /// The meta for the marker types of the variants of the original enum, if they are to be generated.
+    pub(crate) markers: Option<MarkersAttrMeta>,
+    /// The meta for the visitor traits of the derived variants data struct, if they are to be generated.
+    pub(crate) visitor: Option<VisitorAttrMeta>,
+    /// The meta for the handlers struct of the original enum, if it is to be generated.
+    pub(crate) handlers: Option<HandlersAttrMeta>,
+    /// The meta for the router of the original enum, if it is to be generated.
+    pub(crate) router: Option<RouterAttrMeta>,
+    /// The meta for the counters struct of the original enum, if it is to be generated.
+    pub(crate) counters: Option<CountersAttrMeta>,
+    /// The meta for the builder of the derived variants data struct, if it is to be generated.
+    pub(crate) builder: Option<BuilderAttrMeta>,
 }
 
 impl VariantsDataStructAttrMeta {
-    pub(crate) fn from_attrs(attrs: Vec<syn::Attribute>) -> syn::Result<Option<Self>> {
-        let variants_data_struct_attr: syn::Attribute = match attrs
-            .into_iter()
+    pub(crate) fn from_attrs(attrs: &[syn::Attribute]) -> syn::Result<Option<Self>> {
+        let variants_data_struct_attr: &syn::Attribute = match attrs
+            .iter()
             .find(|attr| attr.path().is_ident("variants_data_struct"))
         {
             Some(attr) => attr,
@@ -50,9 +111,61 @@ impl syn::parse::Parse for VariantsDataStructAttrMeta {
         let mut name: Option<syn::Ident> = None;
         let mut attrs: Vec<syn::Attribute> = vec![];
         let mut variants_tys_attrs: Vec<syn::Attribute> = vec![];
+        let mut generics: syn::Generics = syn::Generics::default();
+        let mut field_wrapper: Option<syn::Type> = None;
+        let mut field_wrap: Option<syn::Expr> = None;
+        let mut crate_path: Option<syn::Path> = None;
+        let mut conversions: bool = false;
+        let mut enum_methods: bool = false;
+        let mut traits: bool = false;
+        let mut reflection: bool = false;
+        let mut default: bool = false;
+        let mut accessors: Option<AccessorsAttrMeta> = None;
+        let mut exclude_units: bool = false;
+        let mut only: Option<Vec<syn::Ident>> = None;
+        let mut except: Vec<syn::Ident> = vec![];
+        let mut views: Option<ViewsAttrMeta> = None;
+        let mut kind: Option<KindAttrMeta> = None;
+        let mut options: Option<OptionsAttrMeta> = None;
+        let mut columns: Option<ColumnsAttrMeta> = None;
+        let mut uniform: Option<UniformAttrMeta> = None;
+        let mut family: Option<FamilyAttrMeta> = None;
+        let mut record: Option<RecordAttrMeta> = None;
+        let mut markers: Option<MarkersAttrMeta> = None;
+        let mut visitor: Option<VisitorAttrMeta> = None;
+        let mut handlers: Option<HandlersAttrMeta> = None;
+        let mut router: Option<RouterAttrMeta> = None;
+        let mut counters: Option<CountersAttrMeta> = None;
+        let mut builder: Option<BuilderAttrMeta> = None;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![where]) {
+                let _: syn::Token![where] = input.parse()?;
+                let content;
+                let _paren_token = syn::parenthesized!(content in input);
+                let predicates =
+                    content.parse_terminated(syn::WherePredicate::parse, syn::Token![,])?;
+                generics.make_where_clause().predicates.extend(predicates);
+
+                let lookahead = input.lookahead1();
+                if lookahead.peek(syn::Token![,]) {
+                    let _: syn::Token![,] = input.parse()?;
+                }
+                continue;
+            }
+            if lookahead.peek(syn::Token![crate]) {
+                let _: syn::Token![crate] = input.parse()?;
+                let _: syn::Token![=] = input.parse()?;
+                let path: syn::Path = input.call(syn::Path::parse_mod_style)?;
+                crate_path = Some(path);
+
+                let lookahead = input.lookahead1();
+                if lookahead.peek(syn::Token![,]) {
+                    let _: syn::Token![,] = input.parse()?;
+                }
+                continue;
+            }
             if !lookahead.peek(syn::Ident) {
                 return Err(lookahead.error());
             }
@@ -81,6 +194,198 @@ impl syn::parse::Parse for VariantsDataStructAttrMeta {
                     let _paren_token = syn::parenthesized!(content in input);
                     variants_tys_attrs = content.call(syn::Attribute::parse_outer)?;
                 }
+                "generics" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let syn::Generics {
+                        lt_token,
+                        params,
+                        gt_token,
+                        where_clause: _,
+                    } = content.parse()?;
+                    generics.lt_token = lt_token;
+                    generics.params = params;
+                    generics.gt_token = gt_token;
+                }
+                "field_wrapper" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    field_wrapper = enabled_field_wrapper(parse_field_wrapper(input)?);
+                }
+                "field_wrap" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let field_wrap_expr: syn::Expr = input.parse()?;
+                    field_wrap = Some(field_wrap_expr);
+                }
+                "conversions" => {
+                    conversions = true;
+                }
+                "enum_methods" => {
+                    enum_methods = true;
+                }
+                "traits" => {
+                    traits = true;
+                }
+                "reflection" => {
+                    reflection = true;
+                }
+                "default" => {
+                    default = true;
+                }
+                "accessors" => {
+                    let accessors_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        AccessorsAttrMeta::default()
+                    };
+                    accessors = Some(accessors_attr_meta);
+                }
+                "exclude_units" => {
+                    exclude_units = true;
+                }
+                "only" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let idents = content.parse_terminated(syn::Ident::parse, syn::Token![,])?;
+                    only = Some(idents.into_iter().collect());
+                }
+                "except" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let idents = content.parse_terminated(syn::Ident::parse, syn::Token![,])?;
+                    except = idents.into_iter().collect();
+                }
+                "views" => {
+                    let views_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        ViewsAttrMeta::default()
+                    };
+                    views = Some(views_attr_meta);
+                }
+                "kind" => {
+                    let kind_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        KindAttrMeta::default()
+                    };
+                    kind = Some(kind_attr_meta);
+                }
+                "options" => {
+                    let options_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        OptionsAttrMeta::default()
+                    };
+                    options = Some(options_attr_meta);
+                }
+                "columns" => {
+                    let columns_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        ColumnsAttrMeta::default()
+                    };
+                    columns = Some(columns_attr_meta);
+                }
+                "uniform" => {
+                    let uniform_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        UniformAttrMeta::default()
+                    };
+                    uniform = Some(uniform_attr_meta);
+                }
+                "family" => {
+                    let family_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        FamilyAttrMeta::default()
+                    };
+                    family = Some(family_attr_meta);
+                }
+                "record" => {
+                    let record_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        RecordAttrMeta::default()
+                    };
+                    record = Some(record_attr_meta);
+                }
+                "markers" => {
+                    let markers_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        MarkersAttrMeta::default()
+                    };
+                    markers = Some(markers_attr_meta);
+                }
+                "visitor" => {
+                    let visitor_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        VisitorAttrMeta::default()
+                    };
+                    visitor = Some(visitor_attr_meta);
+                }
+                "handlers" => {
+                    let handlers_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        HandlersAttrMeta::default()
+                    };
+                    handlers = Some(handlers_attr_meta);
+                }
+                "router" => {
+                    let router_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        RouterAttrMeta::default()
+                    };
+                    router = Some(router_attr_meta);
+                }
+                "counters" => {
+                    let counters_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        CountersAttrMeta::default()
+                    };
+                    counters = Some(counters_attr_meta);
+                }
+                "builder" => {
+                    let builder_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        BuilderAttrMeta::default()
+                    };
+                    builder = Some(builder_attr_meta);
+                }
                 _ => {
                     return Err(syn::Error::new_spanned(
                         ident,
@@ -100,6 +405,32 @@ impl syn::parse::Parse for VariantsDataStructAttrMeta {
             variants_tys_attrs,
             name,
             attrs,
+            generics,
+            field_wrapper,
+            field_wrap,
+            crate_path,
+            conversions,
+            enum_methods,
+            traits,
+            reflection,
+            default,
+            accessors,
+            exclude_units,
+            only,
+            except,
+            views,
+            kind,
+            options,
+            columns,
+            uniform,
+            family,
+            record,
+            markers,
+            visitor,
+            handlers,
+            router,
+            counters,
+            builder,
         })
     }
 }
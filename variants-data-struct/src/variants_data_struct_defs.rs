@@ -1,9 +1,11 @@
-use crate::variants_data_struct_field_attr_meta::VariantsDataStructFieldAttrMeta;
-use crate::variants_data_struct_field_meta::VariantsDataStructFieldMeta;
+use crate::generics::{UsedGenericParams, phantom_marker_ident};
+use crate::original_enum::OriginalVariant;
+use crate::variants_data_struct_field_meta::{VariantTy, VariantsDataStructFieldMeta};
 
 pub(crate) struct VariantsDataStructDefs {
     pub(crate) derived_struct: syn::ItemStruct,
     pub(crate) variant_type_structs: Vec<syn::ItemStruct>,
+    pub(crate) variant_type_impls: Vec<syn::Item>,
 }
 
 struct VariantData {
@@ -12,6 +14,7 @@ struct VariantData {
     field_name: syn::Ident,
     field_ty: syn::Type,
     variant_ty_def: Option<syn::ItemStruct>,
+    variant_ty_impls: Vec<syn::Item>,
 }
 
 /// Generates a field for the variants data struct.
@@ -37,6 +40,120 @@ fn variants_data_struct_field(
     }
 }
 
+/// Generates the implementations for the "variant type" of the variant, i.e.
+///
+/// * the `const fn new(...)` function, which takes the fields in the declaration order,
+/// * for tuple variants, the `into_inner(self)` method and the conversions from and into the tuple of the fields,
+/// * if requested with `deref = true`, the `Deref`, `DerefMut` and `AsRef` implementations to its only field.
+fn variant_ty_impls(variant: &OriginalVariant, variant_ty: &VariantTy) -> Vec<syn::Item> {
+    let vis = &variant_ty.vis;
+    let variant_ty_ty = variant_ty.ty();
+    let (impl_generics, _ty_generics, where_clause) = variant_ty.generics.split_for_impl();
+
+    let bindings = variant.bindings();
+    let field_tys: Vec<&syn::Type> = variant.fields.iter().map(|field| &field.ty).collect();
+    let payload_pattern = variant.payload_pattern();
+    let payload_expr = variant.payload_expr();
+
+    let mut items: Vec<syn::Item> = vec![];
+
+    let inherent_items = match &variant.fields {
+        syn::Fields::Unnamed(_) => {
+            let (inner_ty, inner_expr): (syn::Type, syn::Expr) =
+                match (&bindings[..], &field_tys[..]) {
+                    ([binding], [field_ty]) => {
+                        (syn::parse_quote!(#field_ty), syn::parse_quote!(#binding))
+                    }
+                    _ => (
+                        syn::parse_quote!((#(#field_tys,)*)),
+                        syn::parse_quote!((#(#bindings,)*)),
+                    ),
+                };
+            quote::quote! {
+                /// Returns the fields of the variant type, as a single value or a tuple.
+                #vis fn into_inner(self) -> #inner_ty {
+                    let #payload_pattern = self;
+                    #inner_expr
+                }
+            }
+        }
+        syn::Fields::Named(_) | syn::Fields::Unit => quote::quote!(),
+    };
+
+    items.push(syn::parse_quote! {
+        impl #impl_generics #variant_ty_ty #where_clause {
+            /// Creates the variant type from its fields in the declaration order.
+            #[allow(clippy::too_many_arguments)]
+            #vis const fn new(#(#bindings: #field_tys),*) -> Self {
+                #payload_expr
+            }
+
+            #inherent_items
+        }
+    });
+
+    if let syn::Fields::Unnamed(_) = &variant.fields {
+        items.push(syn::parse_quote! {
+            impl #impl_generics ::core::convert::From<(#(#field_tys,)*)> for #variant_ty_ty #where_clause {
+                fn from((#(#bindings,)*): (#(#field_tys,)*)) -> Self {
+                    #payload_expr
+                }
+            }
+        });
+        items.push(syn::parse_quote! {
+            impl #impl_generics ::core::convert::From<#variant_ty_ty> for (#(#field_tys,)*) #where_clause {
+                fn from(value: #variant_ty_ty) -> Self {
+                    let #payload_pattern = value;
+                    (#(#bindings,)*)
+                }
+            }
+        });
+    }
+
+    if variant_ty.deref {
+        let member = variant.members().remove(0);
+        let field_ty = field_tys[0];
+        items.push(syn::parse_quote! {
+            impl #impl_generics ::core::ops::Deref for #variant_ty_ty #where_clause {
+                type Target = #field_ty;
+
+                fn deref(&self) -> &Self::Target {
+                    &self.#member
+                }
+            }
+        });
+        items.push(syn::parse_quote! {
+            impl #impl_generics ::core::ops::DerefMut for #variant_ty_ty #where_clause {
+                fn deref_mut(&mut self) -> &mut Self::Target {
+                    &mut self.#member
+                }
+            }
+        });
+        items.push(syn::parse_quote! {
+            impl #impl_generics ::core::convert::AsRef<#field_ty> for #variant_ty_ty #where_clause {
+                fn as_ref(&self) -> &#field_ty {
+                    &self.#member
+                }
+            }
+        });
+    }
+
+    items
+}
+
+/// Returns the type of the [`PhantomData`](core::marker::PhantomData) marker field of the variants data struct,
+/// which is added when some lifetime or type parameters of the data struct are not used by its fields.
+pub(crate) fn variants_data_struct_marker_ty(
+    variants_data_struct_generics: &syn::Generics,
+    variants: &[OriginalVariant],
+) -> Option<syn::Type> {
+    let mut used_generic_params = UsedGenericParams::new(variants_data_struct_generics);
+    variants
+        .iter()
+        .for_each(|variant| used_generic_params.visit_type(&variant.field_meta.field_ty));
+    used_generic_params.phantom_marker_ty()
+}
+
 /// Generates the variants data struct definitions, including
 ///
 /// * the data variants struct itself and
@@ -44,53 +161,46 @@ fn variants_data_struct_field(
 pub(crate) fn variants_data_struct_defs(
     // The attributes to be applied to the derived variants data struct.
     attrs: Vec<syn::Attribute>,
-    // The attributes to be applied to the "variant types",
-    // which are the generated types for the respective original enum's variants.
-    variants_tys_attrs: Vec<syn::Attribute>,
     // The visibility of the derived variants data struct.
     variants_data_struct_vis: syn::Visibility,
     // The name of the derived variants data struct.
     struct_name: syn::Ident,
-    // The generics of the original enum,
-    // meant to be used for inferring the generics of the "variant types".
-    enum_generics: syn::Generics,
-    // The variants of the original enum.
-    variants: syn::punctuated::Punctuated<syn::Variant, syn::Token![,]>,
-) -> syn::Result<VariantsDataStructDefs> {
-    let variant_data_iter = variants.into_iter().map(|variant| {
-        // Parse the `variant_field` attribute meta for the variant
-        let variants_data_struct_field_attr_meta: VariantsDataStructFieldAttrMeta =
-            VariantsDataStructFieldAttrMeta::from_attrs(&variant.attrs)?.unwrap_or_default();
-
-        // Resolve the final metadata for the variant field and the variant type
+    // The generics of the derived variants data struct.
+    variants_data_struct_generics: syn::Generics,
+    // The variants of the original enum with the resolved metadata for their fields and variant types.
+    variants: &[OriginalVariant],
+) -> VariantsDataStructDefs {
+    let variant_data_iter = variants.iter().map(|variant| {
         let VariantsDataStructFieldMeta {
             field_attrs,
             field_vis,
             field_name,
             field_ty,
             variant_ty,
-        } = VariantsDataStructFieldMeta::resolve(
-            variants_data_struct_field_attr_meta,
-            &variants_tys_attrs,
-            variants_data_struct_vis.clone(),
-            &variant,
-        );
+            ..
+        } = &variant.field_meta;
 
         // Generate the variant type definition, if applicable
-        let variant_ty_def = variant_ty.map(|variant_ty| variant_ty.to_struct_def(variant.fields));
+        let variant_ty_def = variant_ty
+            .as_ref()
+            .map(|variant_ty| variant_ty.to_struct_def(&variant.fields));
+        let variant_ty_impls = variant_ty
+            .as_ref()
+            .map(|variant_ty| variant_ty_impls(variant, variant_ty))
+            .unwrap_or_default();
 
-        let variant_data = VariantData {
-            field_attrs,
-            field_vis,
-            field_name,
-            field_ty,
+        VariantData {
+            field_attrs: field_attrs.clone(),
+            field_vis: field_vis.clone(),
+            field_name: field_name.clone(),
+            field_ty: field_ty.clone(),
             variant_ty_def,
-        };
-
-        syn::Result::Ok(variant_data)
+            variant_ty_impls,
+        }
     });
 
     let mut variant_ty_defs: Vec<syn::ItemStruct> = vec![];
+    let mut variant_ty_impls: Vec<syn::Item> = vec![];
     let mut struct_fields: Vec<syn::Field> = vec![];
 
     for variant_data in variant_data_iter {
@@ -100,17 +210,29 @@ pub(crate) fn variants_data_struct_defs(
             field_vis,
             field_ty,
             variant_ty_def,
-        } = variant_data?;
+            variant_ty_impls: impls,
+        } = variant_data;
 
         if let Some(def) = variant_ty_def {
             variant_ty_defs.push(def);
         }
+        variant_ty_impls.extend(impls);
 
-        let field =
-            variants_data_struct_field(field_attrs, field_vis, field_name.clone(), field_ty);
+        let field = variants_data_struct_field(field_attrs, field_vis, field_name, field_ty);
 
         struct_fields.push(field);
     }
+    // Mark the generic parameters of the data struct that no field uses
+    if let Some(marker_ty) =
+        variants_data_struct_marker_ty(&variants_data_struct_generics, variants)
+    {
+        struct_fields.push(variants_data_struct_field(
+            vec![syn::parse_quote!(#[doc(hidden)])],
+            variants_data_struct_vis.clone(),
+            phantom_marker_ident(),
+            marker_ty,
+        ));
+    }
 
     let delim_span: proc_macro2::extra::DelimSpan = {
         let group = proc_macro2::Group::new(
@@ -127,7 +249,7 @@ pub(crate) fn variants_data_struct_defs(
             span: struct_name.span(),
         },
         ident: struct_name,
-        generics: enum_generics,
+        generics: variants_data_struct_generics,
         fields: syn::Fields::Named(syn::FieldsNamed {
             brace_token: syn::token::Brace { span: delim_span },
             named: syn::punctuated::Punctuated::from_iter(struct_fields),
@@ -135,8 +257,9 @@ pub(crate) fn variants_data_struct_defs(
         semi_token: None,
     };
 
-    Ok(VariantsDataStructDefs {
+    VariantsDataStructDefs {
         derived_struct,
         variant_type_structs: variant_ty_defs,
-    })
+        variant_type_impls: variant_ty_impls,
+    }
 }
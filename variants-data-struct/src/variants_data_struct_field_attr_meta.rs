@@ -1,31 +1,29 @@
+use crate::accessors::AccessorsAttrMeta;
+use crate::field_wrapper::parse_field_wrapper;
+
 /// The [`syn::Attribute::meta`] for the `variants_data_struct_field` attribute.
 ///
 /// For the resolved values (e.g., with defaults applied), see
 /// [`VariantsDataStructFieldMeta`](crate::variants_data_struct_field_meta::VariantsDataStructFieldMeta).
+#[derive(Default)]
 pub(crate) struct VariantsDataStructFieldAttrMeta {
     pub(crate) field_attrs: Vec<syn::Attribute>,
     pub(crate) field_vis: Option<syn::Visibility>,
     pub(crate) field_name: Option<syn::Ident>,
     pub(crate) field_ty_override: Option<syn::Type>,
+    pub(crate) field_from: Option<syn::Expr>,
     pub(crate) gen_variant_ty: Option<bool>,
     pub(crate) variant_ty_attrs: Vec<syn::Attribute>,
     pub(crate) variant_ty_vis: Option<syn::Visibility>,
     pub(crate) variant_ty_name: Option<syn::Ident>,
-}
-
-impl Default for VariantsDataStructFieldAttrMeta {
-    fn default() -> Self {
-        Self {
-            field_attrs: vec![],
-            field_vis: None,
-            field_name: None,
-            field_ty_override: None,
-            gen_variant_ty: None,
-            variant_ty_attrs: vec![],
-            variant_ty_vis: None,
-            variant_ty_name: None,
-        }
-    }
+    pub(crate) variant_ty_generics: syn::Generics,
+    pub(crate) conversions: Option<bool>,
+    pub(crate) field_wrapper: Option<syn::Type>,
+    pub(crate) field_wrap: Option<syn::Expr>,
+    pub(crate) default: Option<syn::Expr>,
+    pub(crate) deref: bool,
+    pub(crate) accessors: Option<AccessorsAttrMeta>,
+    pub(crate) skip: bool,
 }
 
 impl VariantsDataStructFieldAttrMeta {
@@ -43,16 +41,74 @@ impl VariantsDataStructFieldAttrMeta {
     }
 }
 
+/// Returns the default expression of a field of a variant from its `variants_data_struct_field` attribute,
+/// e.g. `42` for `#[variants_data_struct_field(default = 42)]`, if specified.
+///
+/// Unlike the attribute of a variant, the attribute of a field of a variant only accepts the `default` item.
+pub(crate) fn inner_field_default_from_attrs(
+    attrs: &[syn::Attribute],
+) -> syn::Result<Option<syn::Expr>> {
+    let variants_data_struct_field_attr: &syn::Attribute = match attrs
+        .iter()
+        .find(|attr| attr.path().is_ident("variants_data_struct_field"))
+    {
+        Some(attr) => attr,
+        None => return Ok(None),
+    };
+
+    variants_data_struct_field_attr.parse_args_with(|input: syn::parse::ParseStream| {
+        let mut default: Option<syn::Expr> = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+
+            match ident.to_string().as_str() {
+                "default" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let default_expr: syn::Expr = input.parse()?;
+                    default = Some(default_expr);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in variant_field attribute of a field, only default is supported",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(default)
+    })
+}
+
 impl syn::parse::Parse for VariantsDataStructFieldAttrMeta {
     fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
         let mut field_attrs: Vec<syn::Attribute> = vec![];
         let mut field_vis: Option<syn::Visibility> = None;
         let mut field_name: Option<syn::Ident> = None;
         let mut field_ty_override: Option<syn::Type> = None;
+        let mut field_from: Option<syn::Expr> = None;
         let mut gen_variant_ty: Option<bool> = None;
         let mut variant_ty_attrs: Vec<syn::Attribute> = vec![];
         let mut variant_ty_vis: Option<syn::Visibility> = None;
         let mut variant_ty_name: Option<syn::Ident> = None;
+        let mut variant_ty_generics: syn::Generics = syn::Generics::default();
+        let mut conversions: Option<bool> = None;
+        let mut field_wrapper: Option<syn::Type> = None;
+        let mut field_wrap: Option<syn::Expr> = None;
+        let mut default: Option<syn::Expr> = None;
+        let mut deref: bool = false;
+        let mut accessors: Option<AccessorsAttrMeta> = None;
+        let mut skip: bool = false;
 
         while !input.is_empty() {
             let lookahead = input.lookahead1();
@@ -84,6 +140,11 @@ impl syn::parse::Parse for VariantsDataStructFieldAttrMeta {
                     let ty: syn::Type = input.parse()?;
                     field_ty_override = Some(ty);
                 }
+                "field_from" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let field_from_expr: syn::Expr = input.parse()?;
+                    field_from = Some(field_from_expr);
+                }
                 "gen_variant_ty" => {
                     let _: syn::Token![=] = input.parse()?;
                     let gen_variant_ty_lit: syn::LitBool = input.parse()?;
@@ -104,6 +165,66 @@ impl syn::parse::Parse for VariantsDataStructFieldAttrMeta {
                     let name: syn::Ident = input.parse()?;
                     variant_ty_name = Some(name);
                 }
+                "variant_ty_generics" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let syn::Generics {
+                        lt_token,
+                        params,
+                        gt_token,
+                        where_clause: _,
+                    } = content.parse()?;
+                    variant_ty_generics.lt_token = lt_token;
+                    variant_ty_generics.params = params;
+                    variant_ty_generics.gt_token = gt_token;
+                }
+                "variant_ty_where" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    let predicates =
+                        content.parse_terminated(syn::WherePredicate::parse, syn::Token![,])?;
+                    variant_ty_generics
+                        .make_where_clause()
+                        .predicates
+                        .extend(predicates);
+                }
+                "conversions" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let conversions_lit: syn::LitBool = input.parse()?;
+                    conversions = Some(conversions_lit.value());
+                }
+                "field_wrapper" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    field_wrapper = Some(parse_field_wrapper(input)?);
+                }
+                "field_wrap" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let field_wrap_expr: syn::Expr = input.parse()?;
+                    field_wrap = Some(field_wrap_expr);
+                }
+                "default" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let default_expr: syn::Expr = input.parse()?;
+                    default = Some(default_expr);
+                }
+                "deref" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let deref_lit: syn::LitBool = input.parse()?;
+                    deref = deref_lit.value();
+                }
+                "accessors" => {
+                    let accessors_attr_meta = if input.peek(syn::token::Paren) {
+                        let content;
+                        let _paren_token = syn::parenthesized!(content in input);
+                        content.parse()?
+                    } else {
+                        AccessorsAttrMeta::default()
+                    };
+                    accessors = Some(accessors_attr_meta);
+                }
+                "skip" => {
+                    skip = true;
+                }
                 _ => {
                     return Err(syn::Error::new_spanned(
                         ident,
@@ -123,10 +244,19 @@ impl syn::parse::Parse for VariantsDataStructFieldAttrMeta {
             field_vis,
             field_name,
             field_ty_override,
+            field_from,
             gen_variant_ty,
             variant_ty_attrs,
             variant_ty_vis,
             variant_ty_name,
+            variant_ty_generics,
+            conversions,
+            field_wrapper,
+            field_wrap,
+            default,
+            deref,
+            accessors,
+            skip,
         })
     }
 }
@@ -1,35 +1,84 @@
+use crate::accessors::AccessorsMeta;
+use crate::field_wrapper::{default_field_wrap, enabled_field_wrapper, wrap_field_ty};
+use crate::generics::{
+    UsedGenericParams, merge_generics, phantom_marker_member, push_phantom_marker,
+};
 use crate::variants_data_struct_field_attr_meta::VariantsDataStructFieldAttrMeta;
+use crate::variants_data_struct_meta::VariantsDataStructMeta;
 
 pub(crate) struct VariantTy {
     pub(crate) attrs: Vec<syn::Attribute>,
     pub(crate) vis: syn::Visibility,
     pub(crate) name: syn::Ident,
+    /// The generic parameters of the original enum used by the fields of the variant,
+    /// together with the relevant bounds, merged with the additional generics of the "variant type".
+    pub(crate) generics: syn::Generics,
+    /// The type of the [`PhantomData`](core::marker::PhantomData) marker field, which is added
+    /// when some lifetime or type parameters of the "variant type" are not used by the fields of the variant.
+    pub(crate) marker_ty: Option<syn::Type>,
+    /// Whether to implement `Deref`, `DerefMut` and `AsRef` to the only field of the "variant type".
+    pub(crate) deref: bool,
 }
 
 impl VariantTy {
-    pub(crate) fn to_struct_def(self, mut fields: syn::Fields) -> syn::ItemStruct {
-        let VariantTy { attrs, vis, name } = self;
+    /// Returns the type of the "variant type" with its generic parameters applied, e.g. `DataVariantType<T>`.
+    pub(crate) fn ty(&self) -> syn::Type {
+        let name = &self.name;
+        let (_impl_generics, ty_generics, _where_clause) = self.generics.split_for_impl();
+        syn::parse_quote!(#name #ty_generics)
+    }
+
+    /// Returns the member of the [`PhantomData`](core::marker::PhantomData) marker field of the "variant type"
+    /// for the variant with the given fields, if there is one.
+    pub(crate) fn marker_member(&self, fields: &syn::Fields) -> Option<syn::Member> {
+        self.marker_ty.as_ref()?;
+        Some(phantom_marker_member(fields))
+    }
+
+    pub(crate) fn to_struct_def(&self, fields: &syn::Fields) -> syn::ItemStruct {
+        let VariantTy {
+            attrs,
+            vis,
+            name,
+            generics,
+            marker_ty,
+            ..
+        } = self;
+        let mut fields = fields.clone();
+
+        // The marker has the visibility of the "variant type", so that it can be constructed wherever it is visible
+        if let Some(marker_ty) = marker_ty.clone() {
+            push_phantom_marker(&mut fields, vis, marker_ty);
+        }
 
+        // The `variants_data_struct_field` attributes of the fields (e.g. with `default = ...`)
+        // are only meaningful within the original enum
         match fields {
             syn::Fields::Unit => (),
             syn::Fields::Named(ref mut named_fields) => {
                 named_fields.named.iter_mut().for_each(|field| {
                     field.vis = vis.clone();
+                    field
+                        .attrs
+                        .retain(|attr| !attr.path().is_ident("variants_data_struct_field"));
                 });
             }
             syn::Fields::Unnamed(ref mut unnamed_fields) => {
                 unnamed_fields.unnamed.iter_mut().for_each(|field| {
                     field.vis = vis.clone();
+                    field
+                        .attrs
+                        .retain(|attr| !attr.path().is_ident("variants_data_struct_field"));
                 });
             }
         }
 
         syn::ItemStruct {
-            attrs,
-            vis,
+            attrs: attrs.clone(),
+            vis: vis.clone(),
             struct_token: syn::token::Struct { span: name.span() },
-            ident: name,
-            generics: syn::Generics::default(),
+            ident: name.clone(),
+            generics: generics.clone(),
             fields,
             semi_token: None,
         }
@@ -46,16 +95,33 @@ pub(crate) struct VariantsDataStructFieldMeta {
     pub(crate) field_vis: syn::Visibility,
     pub(crate) field_name: syn::Ident,
     pub(crate) field_ty: syn::Type,
+    /// Whether the type of the field in the data struct is overridden with `field_ty_override`.
+    pub(crate) field_ty_overridden: bool,
+    /// The function or closure that converts the "payload" of the variant into the overridden type of the field,
+    /// if provided with `field_from`.
+    pub(crate) field_from: Option<syn::Expr>,
+    /// The type of the field in the data struct before wrapping it with the field wrapper (e.g. `Option<_>`),
+    /// if the field is wrapped.
+    pub(crate) unwrapped_field_ty: Option<syn::Type>,
+    /// The function or closure that wraps the value of the field with the field wrapper, if the field is wrapped
+    /// and the wrapping is either provided with `field_wrap` or known, e.g. `Into::into` for `Option<_>`.
+    pub(crate) field_wrap: Option<syn::Expr>,
     pub(crate) variant_ty: Option<VariantTy>,
+    /// Whether to generate the conversions between the original enum and the "variant type".
+    pub(crate) conversions: bool,
+    /// The expression of the field in the `Default` implementation of the data struct, if specified.
+    pub(crate) default: Option<syn::Expr>,
+    /// The resolved metadata for the accessors of the field, if they are to be generated.
+    pub(crate) accessors: Option<AccessorsMeta>,
 }
 
 impl VariantsDataStructFieldMeta {
     pub(crate) fn resolve(
         attr_meta: VariantsDataStructFieldAttrMeta,
-        variants_tys_attrs: &[syn::Attribute],
-        variants_data_struct_vis: syn::Visibility,
+        variants_data_struct_meta: &VariantsDataStructMeta,
+        enum_generics: &syn::Generics,
         variant: &syn::Variant,
-    ) -> VariantsDataStructFieldMeta {
+    ) -> syn::Result<VariantsDataStructFieldMeta> {
         use convert_case::Casing as _;
 
         let VariantsDataStructFieldAttrMeta {
@@ -63,12 +129,31 @@ impl VariantsDataStructFieldMeta {
             field_vis,
             field_name,
             field_ty_override,
+            field_from,
             gen_variant_ty,
             mut variant_ty_attrs,
             variant_ty_vis,
             variant_ty_name,
+            variant_ty_generics,
+            conversions,
+            field_wrapper,
+            field_wrap,
+            default,
+            deref,
+            accessors,
+            skip: _,
         } = attr_meta;
 
+        let VariantsDataStructMeta {
+            vis: variants_data_struct_vis,
+            variants_tys_attrs,
+            field_wrapper: field_wrapper_by_default,
+            field_wrap: field_wrap_by_default,
+            conversions: conversions_by_default,
+            accessors: accessors_by_default,
+            ..
+        } = variants_data_struct_meta;
+
         variant_ty_attrs.extend_from_slice(variants_tys_attrs);
 
         let field_vis = match field_vis {
@@ -79,7 +164,7 @@ impl VariantsDataStructFieldMeta {
         let field_name = match field_name {
             Some(name) => name,
             None => syn::Ident::new(
-                &variant
+                variant
                     .ident
                     .to_string()
                     .from_case(convert_case::Case::Pascal)
@@ -95,8 +180,8 @@ impl VariantsDataStructFieldMeta {
                 field_ty_override.is_none()
                     && match &variant.fields {
                         syn::Fields::Unit => false,
-                        syn::Fields::Named(named_fields) => named_fields.named.len() > 0,
-                        syn::Fields::Unnamed(unnamed_fields) => unnamed_fields.unnamed.len() > 0,
+                        syn::Fields::Named(named_fields) => !named_fields.named.is_empty(),
+                        syn::Fields::Unnamed(unnamed_fields) => !unnamed_fields.unnamed.is_empty(),
                     }
             }
         };
@@ -106,7 +191,7 @@ impl VariantsDataStructFieldMeta {
         } else {
             let variant_ty_vis = match variant_ty_vis {
                 Some(vis) => vis,
-                None => variants_data_struct_vis,
+                None => variants_data_struct_vis.clone(),
             };
 
             let variant_ty_name = match variant_ty_name {
@@ -117,20 +202,41 @@ impl VariantsDataStructFieldMeta {
                 ),
             };
 
+            let inferred_generics = {
+                let mut used_generic_params = UsedGenericParams::new(enum_generics);
+                variant
+                    .fields
+                    .iter()
+                    .for_each(|field| used_generic_params.visit_type(&field.ty));
+                used_generic_params.to_generics()
+            };
+            let variant_ty_generics = merge_generics(inferred_generics, variant_ty_generics);
+
+            let marker_ty = {
+                let mut used_generic_params = UsedGenericParams::new(&variant_ty_generics);
+                variant
+                    .fields
+                    .iter()
+                    .for_each(|field| used_generic_params.visit_type(&field.ty));
+                used_generic_params.phantom_marker_ty()
+            };
+
             Some(VariantTy {
                 attrs: variant_ty_attrs,
                 vis: variant_ty_vis,
                 name: variant_ty_name,
+                generics: variant_ty_generics,
+                marker_ty,
+                deref,
             })
         };
 
+        let field_ty_overridden = field_ty_override.is_some();
+
         let field_ty = match field_ty_override {
             Some(ty) => ty,
             None => match &variant_ty {
-                Some(variant_ty) => syn::Type::Path(syn::TypePath {
-                    qself: None,
-                    path: syn::Path::from(variant_ty.name.clone()),
-                }),
+                Some(variant_ty) => variant_ty.ty(),
                 None => match &variant.fields {
                     syn::Fields::Unit => syn::Type::Tuple(syn::TypeTuple {
                         paren_token: syn::token::Paren {
@@ -144,10 +250,11 @@ impl VariantsDataStructFieldMeta {
                         },
                         elems: syn::punctuated::Punctuated::new(),
                     }),
-                    syn::Fields::Named(_named_fields) => {
-                        panic!(
-                            "field_ty_override is required for named fields if gen_variant_ty is false"
-                        )
+                    syn::Fields::Named(named_fields) => {
+                        return Err(syn::Error::new_spanned(
+                            named_fields,
+                            "field_ty_override is required for named fields if gen_variant_ty is false",
+                        ));
                     }
                     syn::Fields::Unnamed(unnamed_fields) => syn::Type::Tuple(syn::TypeTuple {
                         paren_token: syn::token::Paren {
@@ -169,12 +276,50 @@ impl VariantsDataStructFieldMeta {
             },
         };
 
-        VariantsDataStructFieldMeta {
+        // The wrapping function of the enum only applies to the template of the enum
+        let (field_wrapper, field_wrap) = match field_wrapper {
+            Some(field_wrapper) => (enabled_field_wrapper(field_wrapper), field_wrap),
+            None => (
+                field_wrapper_by_default.clone(),
+                field_wrap.or_else(|| field_wrap_by_default.clone()),
+            ),
+        };
+
+        let (field_ty, unwrapped_field_ty, field_wrap) = match field_wrapper {
+            Some(field_wrapper) => (
+                wrap_field_ty(&field_wrapper, &field_ty),
+                Some(field_ty),
+                field_wrap.or_else(|| default_field_wrap(&field_wrapper)),
+            ),
+            None => (field_ty, None, None),
+        };
+
+        let conversions = conversions.unwrap_or(*conversions_by_default);
+
+        // The visibilities that aren't specified for the variant are those specified for the enum, if any
+        let accessors = match (accessors, accessors_by_default) {
+            (Some(accessors), Some(accessors_by_default)) => Some(
+                AccessorsMeta::resolve_with_defaults(accessors, accessors_by_default),
+            ),
+            (Some(accessors), None) => {
+                Some(AccessorsMeta::resolve(accessors, variants_data_struct_vis))
+            }
+            (None, accessors_by_default) => accessors_by_default.clone(),
+        };
+
+        Ok(VariantsDataStructFieldMeta {
             field_attrs,
             field_vis,
             field_name,
             field_ty,
+            field_ty_overridden,
+            field_from,
+            unwrapped_field_ty,
+            field_wrap,
             variant_ty,
-        }
+            conversions,
+            default,
+            accessors,
+        })
     }
 }
@@ -1,11 +1,25 @@
 use crate::VariantsDataStructAttrMeta;
+use crate::accessors::AccessorsMeta;
+use crate::builder::BuilderMeta;
+use crate::columns::ColumnsMeta;
+use crate::counters::CountersMeta;
+use crate::family::FamilyMeta;
+use crate::generics::merge_generics;
+use crate::handlers::HandlersMeta;
+use crate::kind::{KindAttrMeta, KindMeta};
+use crate::markers::MarkersMeta;
+use crate::options::OptionsMeta;
+use crate::record::RecordMeta;
+use crate::router::RouterMeta;
+use crate::uniform::{UniformAttrMeta, UniformMeta};
+use crate::views::{ViewsAttrMeta, ViewsMeta};
+use crate::visitor::VisitorMeta;
 
 /// Metadata for deriving a variants data struct from an enum.
 ///
 /// For the raw attribute meta (i.e., as parsed from the `variant_field` attribute), see
 ///
 /// [`VariantsDataStructAttrMeta`].
-
 pub(crate) struct VariantsDataStructMeta {
     /// The attributes to be applied to the derived variants data struct.
     pub(crate) attrs: Vec<syn::Attribute>,
@@ -16,6 +30,61 @@ pub(crate) struct VariantsDataStructMeta {
     /// The attributes to be applied to the "variant types",
     /// which are the generated types for the respective original enum's variants.
     pub(crate) variants_tys_attrs: Vec<syn::Attribute>,
+    /// The generics of the derived variants data struct, i.e. the generics of the original enum
+    /// merged with the additional generic parameters and where-clause predicates.
+    pub(crate) generics: syn::Generics,
+    /// The template for wrapping the types of the fields of the derived variants data struct by default, e.g. `Option<_>`.
+    pub(crate) field_wrapper: Option<syn::Type>,
+    /// The function or closure that wraps the values of the fields with the field wrapper by default, if provided.
+    pub(crate) field_wrap: Option<syn::Expr>,
+    /// The path to the `variants-data-struct-runtime` crate.
+    pub(crate) runtime: syn::Path,
+    /// Whether to generate the conversions between the original enum and the "variant types" by default.
+    pub(crate) conversions: bool,
+    /// Whether to generate the per-variant methods of the original enum, e.g. `is_tuple_enum`.
+    pub(crate) enum_methods: bool,
+    /// Whether to implement the traits of the `variants-data-struct-runtime` crate, e.g. `HasVariantsData`.
+    pub(crate) traits: bool,
+    /// Whether to generate the reflection metadata of the original enum, e.g. the `VARIANTS` constant.
+    pub(crate) reflection: bool,
+    /// Whether to implement `Default` for the derived variants data struct and the "variant types".
+    pub(crate) default: bool,
+    /// The resolved metadata for the accessors of the fields of the derived variants data struct by default,
+    /// if they are to be generated.
+    pub(crate) accessors: Option<AccessorsMeta>,
+    /// Whether to skip the unit variants of the original enum.
+    pub(crate) exclude_units: bool,
+    /// The variants of the original enum to keep, if only some of them are to be kept.
+    pub(crate) only: Option<Vec<syn::Ident>>,
+    /// The variants of the original enum to skip.
+    pub(crate) except: Vec<syn::Ident>,
+    /// The resolved metadata for the borrowed and mutably borrowed views of the original enum,
+    /// if they are to be generated.
+    pub(crate) views: Option<ViewsMeta>,
+    /// The resolved metadata for the kind enum of the original enum, if it is to be generated.
+    pub(crate) kind: Option<KindMeta>,
+    /// The resolved metadata for the options struct of the original enum, if it is to be generated.
+    pub(crate) options: Option<OptionsMeta>,
+    /// The resolved metadata for the columns struct of the original enum, if it is to be generated.
+    pub(crate) columns: Option<ColumnsMeta>,
+    /// The resolved metadata for the uniform map struct of the original enum, if it is to be generated.
+    pub(crate) uniform: Option<UniformMeta>,
+    /// The resolved metadata for the family struct of the original enum, if it is to be generated.
+    pub(crate) family: Option<FamilyMeta>,
+    /// The resolved metadata for the record struct of the original enum, if it is to be generated.
+    pub(crate) record: Option<RecordMeta>,
+    /// The resolved metadata for the marker types of the variants of the original enum, if they are to be generated.
+    pub(crate) markers: Option<MarkersMeta>,
+    /// The resolved metadata for the visitor traits of the derived variants data struct, if they are to be generated.
+    pub(crate) visitor: Option<VisitorMeta>,
+    /// The resolved metadata for the handlers struct of the original enum, if it is to be generated.
+    pub(crate) handlers: Option<HandlersMeta>,
+    /// The resolved metadata for the router of the original enum, if it is to be generated.
+    pub(crate) router: Option<RouterMeta>,
+    /// The resolved metadata for the counters struct of the original enum, if it is to be generated.
+    pub(crate) counters: Option<CountersMeta>,
+    /// The resolved metadata for the builder of the derived variants data struct, if it is to be generated.
+    pub(crate) builder: Option<BuilderMeta>,
 }
 
 impl VariantsDataStructMeta {
@@ -23,12 +92,39 @@ impl VariantsDataStructMeta {
         attr_meta: VariantsDataStructAttrMeta,
         enum_ident: &syn::Ident,
         enum_vis: &syn::Visibility,
+        enum_generics: &syn::Generics,
     ) -> VariantsDataStructMeta {
         let VariantsDataStructAttrMeta {
             attrs,
             vis,
             name,
             variants_tys_attrs,
+            generics,
+            field_wrapper,
+            field_wrap,
+            crate_path,
+            conversions,
+            enum_methods,
+            traits,
+            reflection,
+            default,
+            accessors,
+            exclude_units,
+            only,
+            except,
+            views,
+            kind,
+            options,
+            columns,
+            uniform,
+            family,
+            record,
+            markers,
+            visitor,
+            handlers,
+            router,
+            counters,
+            builder,
         } = attr_meta;
 
         let vis = match vis {
@@ -41,11 +137,90 @@ impl VariantsDataStructMeta {
             None => syn::Ident::new(&format!("{enum_ident}VariantsData"), enum_ident.span()),
         };
 
+        let generics = merge_generics(enum_generics.clone(), generics);
+
+        let runtime = match crate_path {
+            Some(crate_path) => crate_path,
+            None => syn::parse_quote!(::variants_data_struct_runtime),
+        };
+
+        let accessors = accessors.map(|accessors| AccessorsMeta::resolve(accessors, &vis));
+
+        // The columns struct yields the borrowed views, so it implies them
+        let views = match (views, &columns) {
+            (Some(views), _) => Some(views),
+            (None, Some(_columns)) => Some(ViewsAttrMeta::default()),
+            (None, None) => None,
+        };
+        let views = views.map(|views| ViewsMeta::resolve(views, enum_ident));
+
+        // The kind enum is the `Kind` of the original enum in `HasVariantsData`, so the traits imply it
+        let kind = match (kind, traits) {
+            (Some(kind), _) => Some(kind),
+            (None, true) => Some(KindAttrMeta::default()),
+            (None, false) => None,
+        };
+        let kind = kind.map(|kind| KindMeta::resolve(kind, enum_ident, &vis));
+
+        let options = options.map(|options| OptionsMeta::resolve(options, enum_ident));
+
+        let columns = columns.map(|columns| ColumnsMeta::resolve(columns, enum_ident));
+
+        // The snapshot of the counters is the uniform map of `u64`, so the counters imply it
+        let uniform = match (uniform, &counters) {
+            (Some(uniform), _) => Some(uniform),
+            (None, Some(_counters)) => Some(UniformAttrMeta::default()),
+            (None, None) => None,
+        };
+        let uniform = uniform.map(|uniform| UniformMeta::resolve(uniform, enum_ident));
+
+        let family = family.map(|family| FamilyMeta::resolve(family, enum_ident));
+
+        let record = record.map(|record| RecordMeta::resolve(record, enum_ident));
+
+        let markers = markers.map(|markers| MarkersMeta::resolve(markers, enum_ident));
+
+        let visitor = visitor.map(|visitor| VisitorMeta::resolve(visitor, enum_ident));
+
+        let handlers = handlers.map(|handlers| HandlersMeta::resolve(handlers, enum_ident));
+
+        let router = router.map(|router| RouterMeta::resolve(router, enum_ident));
+
+        let counters = counters.map(|counters| CountersMeta::resolve(counters, enum_ident));
+
+        let builder = builder.map(|builder| BuilderMeta::resolve(builder, &name));
+
         VariantsDataStructMeta {
             attrs,
             vis,
             name,
             variants_tys_attrs,
+            generics,
+            field_wrapper,
+            field_wrap,
+            runtime,
+            conversions,
+            enum_methods,
+            traits,
+            reflection,
+            default,
+            accessors,
+            exclude_units,
+            only,
+            except,
+            views,
+            kind,
+            options,
+            columns,
+            uniform,
+            family,
+            record,
+            markers,
+            visitor,
+            handlers,
+            router,
+            counters,
+            builder,
         }
     }
 }
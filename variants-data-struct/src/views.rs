@@ -0,0 +1,448 @@
+use crate::generics::{
+    UsedGenericParams, fresh_lifetime, phantom_marker_member, push_phantom_marker, with_lifetime,
+};
+use crate::original_enum::{OriginalEnum, OriginalVariant};
+
+/// The meta of the `views(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`ViewsMeta`].
+#[derive(Default)]
+pub(crate) struct ViewsAttrMeta {
+    /// The override for the name of the borrowed view enum.
+    ///
+    /// If not provided, the default name is `<EnumName>Ref`.
+    pub(crate) ref_name: Option<syn::Ident>,
+    /// The override for the name of the mutably borrowed view enum.
+    ///
+    /// If not provided, the default name is `<EnumName>Mut`.
+    pub(crate) mut_name: Option<syn::Ident>,
+    /// The attributes to be applied to the view enums and the view structs.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl syn::parse::Parse for ViewsAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut ref_name: Option<syn::Ident> = None;
+        let mut mut_name: Option<syn::Ident> = None;
+        let mut attrs: Vec<syn::Attribute> = vec![];
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "ref_name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name: syn::Ident = input.parse()?;
+                    ref_name = Some(name);
+                }
+                "mut_name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name: syn::Ident = input.parse()?;
+                    mut_name = Some(name);
+                }
+                "attrs" => {
+                    let content;
+                    let _paren_token = syn::parenthesized!(content in input);
+                    attrs = content.call(syn::Attribute::parse_outer)?;
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in views of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(ViewsAttrMeta {
+            ref_name,
+            mut_name,
+            attrs,
+        })
+    }
+}
+
+/// The resolved metadata for the borrowed and mutably borrowed views of the original enum.
+///
+/// For the raw attribute meta, see [`ViewsAttrMeta`].
+pub(crate) struct ViewsMeta {
+    /// The name of the borrowed view enum.
+    pub(crate) ref_name: syn::Ident,
+    /// The name of the mutably borrowed view enum.
+    pub(crate) mut_name: syn::Ident,
+    /// The attributes to be applied to the view enums and the view structs.
+    pub(crate) attrs: Vec<syn::Attribute>,
+}
+
+impl ViewsMeta {
+    pub(crate) fn resolve(attr_meta: ViewsAttrMeta, enum_ident: &syn::Ident) -> ViewsMeta {
+        let ViewsAttrMeta {
+            ref_name,
+            mut_name,
+            attrs,
+        } = attr_meta;
+
+        let ref_name = match ref_name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Ref"), enum_ident.span()),
+        };
+
+        let mut_name = match mut_name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}Mut"), enum_ident.span()),
+        };
+
+        ViewsMeta {
+            ref_name,
+            mut_name,
+            attrs,
+        }
+    }
+}
+
+/// The kind of a borrowed view.
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub(crate) enum ViewKind {
+    /// A view with `&'a` references to the fields.
+    Ref,
+    /// A view with `&'a mut` references to the fields.
+    Mut,
+}
+
+impl ViewKind {
+    /// Returns the name of the view struct for the variant, e.g. `TupleEnumVariantRef`.
+    pub(crate) fn view_struct_name(self, variant: &OriginalVariant) -> syn::Ident {
+        let suffix = match self {
+            ViewKind::Ref => "VariantRef",
+            ViewKind::Mut => "VariantMut",
+        };
+        syn::Ident::new(&format!("{}{suffix}", variant.ident), variant.ident.span())
+    }
+
+    /// Returns the reference type to the given type, e.g. `&'a T` or `&'a mut T`.
+    fn reference(self, lifetime: &syn::Lifetime, ty: &syn::Type) -> syn::Type {
+        match self {
+            ViewKind::Ref => syn::parse_quote!(&#lifetime #ty),
+            ViewKind::Mut => syn::parse_quote!(&#lifetime mut #ty),
+        }
+    }
+
+    /// Returns the fields of the variant with their types replaced by references to them.
+    fn view_fields(self, lifetime: &syn::Lifetime, fields: &syn::Fields) -> syn::Fields {
+        let mut fields = fields.clone();
+        fields.iter_mut().for_each(|field| {
+            field.attrs.clear();
+            field.ty = self.reference(lifetime, &field.ty);
+        });
+        fields
+    }
+}
+
+/// Returns the fields of the view struct of the variant's data, including the
+/// [`PhantomData`](core::marker::PhantomData) marker field, if necessary,
+/// together with the member of the marker field, if there is one.
+fn view_struct_fields(
+    kind: ViewKind,
+    lifetime: &syn::Lifetime,
+    variant: &OriginalVariant,
+) -> Option<(syn::Fields, Option<syn::Member>)> {
+    let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+    let generics = with_lifetime(&variant_ty.generics, lifetime);
+
+    let mut fields = kind.view_fields(lifetime, &variant.fields);
+    fields
+        .iter_mut()
+        .for_each(|field| field.vis = variant_ty.vis.clone());
+
+    let mut used_generic_params = UsedGenericParams::new(&generics);
+    fields
+        .iter()
+        .for_each(|field| used_generic_params.visit_type(&field.ty));
+    let marker_member = used_generic_params.phantom_marker_ty().map(|marker_ty| {
+        let marker_member = phantom_marker_member(&fields);
+        push_phantom_marker(&mut fields, &variant_ty.vis, marker_ty);
+        marker_member
+    });
+
+    Some((fields, marker_member))
+}
+
+/// Returns the type of the view of the variant's data, e.g. `TupleEnumVariantRef<'a, T>`,
+/// if the variant has a "variant type".
+pub(crate) fn view_struct_ty(
+    kind: ViewKind,
+    lifetime: &syn::Lifetime,
+    variant: &OriginalVariant,
+) -> Option<syn::Type> {
+    let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+    let name = kind.view_struct_name(variant);
+    let generics = with_lifetime(&variant_ty.generics, lifetime);
+    let (_impl_generics, ty_generics, _where_clause) = generics.split_for_impl();
+    Some(syn::parse_quote!(#name #ty_generics))
+}
+
+/// Returns the fields of the variant of the view enum, e.g. `(TupleEnumVariantRef<'a, T>)` for variants
+/// with "variant types" and the fields of the variant with their types replaced by references to them otherwise.
+fn view_enum_variant_fields(
+    kind: ViewKind,
+    lifetime: &syn::Lifetime,
+    variant: &OriginalVariant,
+) -> syn::Fields {
+    match view_struct_ty(kind, lifetime, variant) {
+        Some(view_struct_ty) => syn::Fields::Unnamed(syn::parse_quote!((#view_struct_ty))),
+        None => kind.view_fields(lifetime, &variant.fields),
+    }
+}
+
+/// Returns the expression that constructs the view of the variant of the original enum
+/// from the bound references to its fields (see [`OriginalVariant::bindings`]).
+pub(crate) fn view_expr(
+    kind: ViewKind,
+    lifetime: &syn::Lifetime,
+    view_enum_name: &syn::Ident,
+    variant: &OriginalVariant,
+) -> syn::Expr {
+    let ident = &variant.ident;
+    let bound_fields = variant.bound_fields();
+    match view_struct_fields(kind, lifetime, variant) {
+        Some((_fields, marker_member)) => {
+            let view_struct_name = kind.view_struct_name(variant);
+            let marker = marker_member
+                .map(|marker_member| quote::quote!(#marker_member: ::core::marker::PhantomData,));
+            syn::parse_quote!(#view_enum_name::#ident(#view_struct_name { #(#bound_fields,)* #marker }))
+        }
+        None => syn::parse_quote!(#view_enum_name::#ident { #(#bound_fields),* }),
+    }
+}
+
+/// Generates the view struct of the variant's data, e.g. `TupleEnumVariantRef<'a>`,
+/// if the variant has a "variant type".
+fn view_struct_def(
+    kind: ViewKind,
+    lifetime: &syn::Lifetime,
+    attrs: &[syn::Attribute],
+    variant: &OriginalVariant,
+) -> Option<syn::ItemStruct> {
+    let variant_ty = variant.field_meta.variant_ty.as_ref()?;
+    let vis = &variant_ty.vis;
+    let name = kind.view_struct_name(variant);
+    let generics = with_lifetime(&variant_ty.generics, lifetime);
+    let (_impl_generics, _ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+    let (fields, _marker_member) = view_struct_fields(kind, lifetime, variant)?;
+
+    let doc = format!(
+        " A {} view of the data of [`{}`].",
+        match kind {
+            ViewKind::Ref => "borrowed",
+            ViewKind::Mut => "mutably borrowed",
+        },
+        variant_ty.name,
+    );
+
+    Some(match fields {
+        syn::Fields::Named(named_fields) => syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #where_clause #named_fields
+        },
+        fields => syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis struct #name<#params> #fields #where_clause;
+        },
+    })
+}
+
+/// Generates the `Clone` and `Copy` implementations for a borrowed view,
+/// which don't require the generic parameters to implement `Clone`.
+fn clone_copy_impls(ty_name: &syn::Ident, generics: &syn::Generics) -> [syn::ItemImpl; 2] {
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    [
+        syn::parse_quote! {
+            impl #impl_generics ::core::clone::Clone for #ty_name #ty_generics #where_clause {
+                fn clone(&self) -> Self {
+                    *self
+                }
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics ::core::marker::Copy for #ty_name #ty_generics #where_clause {}
+        },
+    ]
+}
+
+/// Generates the borrowed and mutably borrowed views of the original enum, i.e.
+///
+/// * the view enums, e.g. `MyEnumRef<'a>` and `MyEnumMut<'a>`,
+/// * the view structs of the variants' data, e.g. `TupleEnumVariantRef<'a>` and `TupleEnumVariantMut<'a>`,
+/// * the `as_variant_ref` and `as_variant_mut` methods of the original enum.
+pub(crate) fn views(
+    // The resolved metadata for the views.
+    views_meta: &ViewsMeta,
+    // The visibility of the view enums and the methods, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> syn::Result<Vec<syn::Item>> {
+    let ViewsMeta {
+        ref_name,
+        mut_name,
+        attrs,
+    } = views_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_generics = &original_enum.generics;
+    let lifetime = fresh_lifetime(enum_generics, "a");
+
+    for variant in &original_enum.variants {
+        if let Some(variant_ty) = &variant.field_meta.variant_ty {
+            let undeclared_param = variant_ty.generics.params.iter().find(|param| {
+                !enum_generics
+                    .params
+                    .iter()
+                    .any(|enum_param| same_generic_param(enum_param, param))
+            });
+            if let Some(param) = undeclared_param {
+                return Err(syn::Error::new_spanned(
+                    param,
+                    "views don't support generic parameters of variant types that the original enum doesn't declare",
+                ));
+            }
+        }
+    }
+
+    let view_generics = with_lifetime(enum_generics, &lifetime);
+    let (_view_impl_generics, view_ty_generics, view_where_clause) = view_generics.split_for_impl();
+    let view_params = &view_generics.params;
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+
+    let mut items: Vec<syn::Item> = vec![];
+
+    for (kind, view_enum_name) in [(ViewKind::Ref, ref_name), (ViewKind::Mut, mut_name)] {
+        let view_variants = original_enum.variants.iter().map(|variant| {
+            let ident = &variant.ident;
+            let fields = view_enum_variant_fields(kind, &lifetime, variant);
+            quote::quote!(#ident #fields)
+        });
+
+        let doc = format!(
+            " A {} view of [`{enum_ident}`].",
+            match kind {
+                ViewKind::Ref => "borrowed",
+                ViewKind::Mut => "mutably borrowed",
+            },
+        );
+
+        items.push(syn::parse_quote! {
+            #[doc = #doc]
+            #(#attrs)*
+            #vis enum #view_enum_name<#view_params> #view_where_clause {
+                #(#view_variants,)*
+            }
+        });
+
+        for variant in &original_enum.variants {
+            if let Some(view_struct_def) = view_struct_def(kind, &lifetime, attrs, variant) {
+                if kind == ViewKind::Ref {
+                    let variant_ty = variant.field_meta.variant_ty.as_ref().unwrap();
+                    let generics = with_lifetime(&variant_ty.generics, &lifetime);
+                    items.extend(
+                        clone_copy_impls(&view_struct_def.ident, &generics).map(syn::Item::Impl),
+                    );
+                }
+                items.push(syn::Item::Struct(view_struct_def));
+            }
+        }
+
+        if kind == ViewKind::Ref {
+            items.extend(clone_copy_impls(view_enum_name, &view_generics).map(syn::Item::Impl));
+        }
+    }
+
+    let (ref_arms, mut_arms): (Vec<syn::Arm>, Vec<syn::Arm>) = original_enum
+        .variants
+        .iter()
+        .map(|variant| {
+            let pattern = variant.pattern(enum_ident);
+            let ref_expr = view_expr(ViewKind::Ref, &lifetime, ref_name, variant);
+            let mut_expr = view_expr(ViewKind::Mut, &lifetime, mut_name, variant);
+            (
+                syn::parse_quote!(#pattern => #ref_expr,),
+                syn::parse_quote!(#pattern => #mut_expr,),
+            )
+        })
+        .unzip();
+
+    let ref_ty: syn::Type = syn::parse_quote!(#ref_name #view_ty_generics);
+    let mut_ty: syn::Type = syn::parse_quote!(#mut_name #view_ty_generics);
+
+    // The skipped variants have no view, so the views of a value are only available for the others
+    let view_methods = match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            #[doc = concat!(" Returns a borrowed view of the variant's data, i.e. [`", stringify!(#ref_name), "`],")]
+            /// or `None` if the variant is skipped.
+            #vis fn as_variant_ref<#lifetime>(&#lifetime self) -> ::core::option::Option<#ref_ty> {
+                ::core::option::Option::Some(match self {
+                    #(#ref_arms)*
+                    #skipped_pattern => return ::core::option::Option::None,
+                })
+            }
+
+            #[doc = concat!(" Returns a mutably borrowed view of the variant's data, i.e. [`", stringify!(#mut_name), "`],")]
+            /// or `None` if the variant is skipped.
+            #vis fn as_variant_mut<#lifetime>(&#lifetime mut self) -> ::core::option::Option<#mut_ty> {
+                ::core::option::Option::Some(match self {
+                    #(#mut_arms)*
+                    #skipped_pattern => return ::core::option::Option::None,
+                })
+            }
+        },
+        None => quote::quote! {
+            #[doc = concat!(" Returns a borrowed view of the variant's data, i.e. [`", stringify!(#ref_name), "`].")]
+            #vis fn as_variant_ref<#lifetime>(&#lifetime self) -> #ref_ty {
+                match self {
+                    #(#ref_arms)*
+                }
+            }
+
+            #[doc = concat!(" Returns a mutably borrowed view of the variant's data, i.e. [`", stringify!(#mut_name), "`].")]
+            #vis fn as_variant_mut<#lifetime>(&#lifetime mut self) -> #mut_ty {
+                match self {
+                    #(#mut_arms)*
+                }
+            }
+        },
+    };
+
+    items.push(syn::parse_quote! {
+        impl #enum_impl_generics #enum_ident #enum_ty_generics #enum_where_clause {
+            #view_methods
+        }
+    });
+
+    Ok(items)
+}
+
+/// Returns `true` if the generic parameters have the same kind and name.
+fn same_generic_param(a: &syn::GenericParam, b: &syn::GenericParam) -> bool {
+    match (a, b) {
+        (syn::GenericParam::Lifetime(a), syn::GenericParam::Lifetime(b)) => {
+            a.lifetime.ident == b.lifetime.ident
+        }
+        (syn::GenericParam::Type(a), syn::GenericParam::Type(b)) => a.ident == b.ident,
+        (syn::GenericParam::Const(a), syn::GenericParam::Const(b)) => a.ident == b.ident,
+        _ => false,
+    }
+}
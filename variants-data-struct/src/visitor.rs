@@ -0,0 +1,266 @@
+use crate::generics::{fresh_type_param, method_generics};
+use crate::original_enum::OriginalEnum;
+
+/// The meta of the `visitor(...)` item of the `variants_data_struct` attribute.
+///
+/// For the resolved values (e.g., with defaults applied), see [`VisitorMeta`].
+#[derive(Default)]
+pub(crate) struct VisitorAttrMeta {
+    /// The override for the name of the visitor trait.
+    ///
+    /// If not provided, the default name is `<EnumName>VariantsVisitor`.
+    pub(crate) name: Option<syn::Ident>,
+    /// The override for the name of the mutable visitor trait.
+    ///
+    /// If not provided, the default name is `<EnumName>VariantsVisitorMut`.
+    pub(crate) mut_name: Option<syn::Ident>,
+}
+
+impl syn::parse::Parse for VisitorAttrMeta {
+    fn parse(input: syn::parse::ParseStream) -> syn::Result<Self> {
+        let mut name: Option<syn::Ident> = None;
+        let mut mut_name: Option<syn::Ident> = None;
+
+        while !input.is_empty() {
+            let lookahead = input.lookahead1();
+            if !lookahead.peek(syn::Ident) {
+                return Err(lookahead.error());
+            }
+            let ident: syn::Ident = input.parse()?;
+            let ident: String = ident.to_string();
+            let ident: &str = ident.as_str();
+
+            match ident {
+                "name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let name_ident: syn::Ident = input.parse()?;
+                    name = Some(name_ident);
+                }
+                "mut_name" => {
+                    let _: syn::Token![=] = input.parse()?;
+                    let mut_name_ident: syn::Ident = input.parse()?;
+                    mut_name = Some(mut_name_ident);
+                }
+                _ => {
+                    return Err(syn::Error::new_spanned(
+                        ident,
+                        "unexpected identifier in visitor of variants_data_struct attribute",
+                    ));
+                }
+            }
+
+            let lookahead = input.lookahead1();
+            if lookahead.peek(syn::Token![,]) {
+                let _: syn::Token![,] = input.parse()?;
+            }
+        }
+
+        Ok(VisitorAttrMeta { name, mut_name })
+    }
+}
+
+/// The resolved metadata for the visitor traits of the derived variants data struct.
+///
+/// For the raw attribute meta, see [`VisitorAttrMeta`].
+pub(crate) struct VisitorMeta {
+    /// The name of the visitor trait.
+    pub(crate) name: syn::Ident,
+    /// The name of the mutable visitor trait.
+    pub(crate) mut_name: syn::Ident,
+}
+
+impl VisitorMeta {
+    pub(crate) fn resolve(attr_meta: VisitorAttrMeta, enum_ident: &syn::Ident) -> VisitorMeta {
+        let VisitorAttrMeta { name, mut_name } = attr_meta;
+
+        let name = match name {
+            Some(name) => name,
+            None => syn::Ident::new(&format!("{enum_ident}VariantsVisitor"), enum_ident.span()),
+        };
+
+        let mut_name = match mut_name {
+            Some(mut_name) => mut_name,
+            None => syn::Ident::new(
+                &format!("{enum_ident}VariantsVisitorMut"),
+                enum_ident.span(),
+            ),
+        };
+
+        VisitorMeta { name, mut_name }
+    }
+}
+
+/// Generates the visitor traits of the derived variants data struct, i.e.
+///
+/// * the `MyEnumVariantsVisitor` trait with a `visit_<field_name>(&mut self, value: &FieldType)` method
+///   for each field of the data struct, which does nothing by default,
+/// * the `MyEnumVariantsVisitorMut` trait with a `visit_<field_name>_mut(&mut self, value: &mut FieldType)` method
+///   for each field of the data struct, which does nothing by default,
+/// * the `accept` and `accept_mut` methods of the data struct, which visit each field in the order of the variants,
+/// * the `accept` method of the original enum, which visits the data of its variant, unless the value of some field
+///   of the data struct can't be constructed (see
+///   [`OriginalVariant::field_value_expr`](crate::original_enum::OriginalVariant::field_value_expr)).
+pub(crate) fn visitor(
+    // The resolved metadata for the visitor traits.
+    visitor_meta: &VisitorMeta,
+    // The visibility of the visitor traits and the methods, i.e. that of the derived variants data struct.
+    vis: &syn::Visibility,
+    // The name of the derived variants data struct.
+    variants_data_struct_name: &syn::Ident,
+    // The generics of the derived variants data struct, which are also the generics of the visitor traits.
+    generics: &syn::Generics,
+    // The original enum with the resolved metadata for its variants.
+    original_enum: &OriginalEnum,
+) -> Vec<syn::Item> {
+    let VisitorMeta { name, mut_name } = visitor_meta;
+
+    let enum_ident = &original_enum.ident;
+    let enum_generics = &original_enum.generics;
+    let (enum_impl_generics, enum_ty_generics, enum_where_clause) = enum_generics.split_for_impl();
+    let (impl_generics, ty_generics, where_clause) = generics.split_for_impl();
+    let params = &generics.params;
+
+    let variants = &original_enum.variants;
+    let field_names: Vec<&syn::Ident> = variants
+        .iter()
+        .map(|variant| &variant.field_meta.field_name)
+        .collect();
+    let visit_fns: Vec<syn::Ident> = field_names
+        .iter()
+        .map(|field_name| quote::format_ident!("visit_{}", field_name))
+        .collect();
+    let visit_mut_fns: Vec<syn::Ident> = field_names
+        .iter()
+        .map(|field_name| quote::format_ident!("visit_{}_mut", field_name))
+        .collect();
+
+    let visit_methods = variants.iter().zip(&visit_fns).map(|(variant, visit_fn)| {
+        let field_ty = &variant.field_meta.field_ty;
+        let doc = format!(" Visits the data of [`{enum_ident}::{}`].", variant.ident);
+        quote::quote! {
+            #[doc = #doc]
+            fn #visit_fn(&mut self, value: &#field_ty) {
+                let _ = value;
+            }
+        }
+    });
+
+    let visit_mut_methods = variants
+        .iter()
+        .zip(&visit_mut_fns)
+        .map(|(variant, visit_mut_fn)| {
+            let field_ty = &variant.field_meta.field_ty;
+            let doc = format!(
+                " Visits the mutable data of [`{enum_ident}::{}`].",
+                variant.ident
+            );
+            quote::quote! {
+                #[doc = #doc]
+                fn #visit_mut_fn(&mut self, value: &mut #field_ty) {
+                    let _ = value;
+                }
+            }
+        });
+
+    // The bindings of the fields may shadow the visitor, so it is hygienic
+    let visitor = syn::Ident::new("visitor", proc_macro2::Span::mixed_site());
+    // `None` if the value of some field can't be constructed, e.g. for `Vec<_>` without `field_wrap`
+    let accept_arms: Option<Vec<proc_macro2::TokenStream>> = variants
+        .iter()
+        .zip(&visit_fns)
+        .map(|(variant, visit_fn)| {
+            let pattern = variant.pattern(enum_ident);
+            let field_value_expr = variant.field_value_expr()?;
+            Some(quote::quote! {
+                #pattern => #visitor.#visit_fn(&#field_value_expr),
+            })
+        })
+        .collect();
+
+    // The data struct may have generic parameters that the original enum doesn't declare
+    let mut accept_generics = method_generics(enum_generics, generics.clone());
+    let visitor_param = fresh_type_param(generics, "V");
+    accept_generics
+        .params
+        .push(syn::parse_quote!(#visitor_param: #name #ty_generics));
+    accept_generics
+        .lt_token
+        .get_or_insert_with(Default::default);
+    accept_generics
+        .gt_token
+        .get_or_insert_with(Default::default);
+    let (accept_impl_generics, _accept_ty_generics, accept_where_clause) =
+        accept_generics.split_for_impl();
+
+    // The skipped variants have no visit method, so their values are returned back
+    let accept_method = accept_arms.map(|accept_arms| match original_enum.skipped_pattern() {
+        Some(skipped_pattern) => quote::quote! {
+            /// Visits the data of the variant with the visitor, consuming the enum,
+            /// or returns the enum back if its variant is skipped.
+            #vis fn accept #accept_impl_generics(
+                self,
+                #visitor: &mut #visitor_param,
+            ) -> ::core::result::Result<(), Self> #accept_where_clause {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#accept_arms)*
+                    value @ (#skipped_pattern) => return ::core::result::Result::Err(value),
+                }
+                ::core::result::Result::Ok(())
+            }
+        },
+        None => quote::quote! {
+            /// Visits the data of the variant with the visitor, consuming the enum.
+            #vis fn accept #accept_impl_generics(
+                self,
+                #visitor: &mut #visitor_param,
+            ) #accept_where_clause {
+                #[allow(unreachable_patterns)]
+                match self {
+                    #(#accept_arms)*
+                }
+            }
+        },
+    });
+
+    let doc = format!(" The visitor of the data of the variants of [`{enum_ident}`].");
+    let mut_doc = format!(" The visitor of the mutable data of the variants of [`{enum_ident}`].");
+
+    let mut items: Vec<syn::Item> = vec![
+        syn::parse_quote! {
+            #[doc = #doc]
+            #vis trait #name<#params> #where_clause {
+                #(#visit_methods)*
+            }
+        },
+        syn::parse_quote! {
+            #[doc = #mut_doc]
+            #vis trait #mut_name<#params> #where_clause {
+                #(#visit_mut_methods)*
+            }
+        },
+        syn::parse_quote! {
+            impl #impl_generics #variants_data_struct_name #ty_generics #where_clause {
+                /// Visits each field with the visitor in the order of the variants.
+                #vis fn accept(&self, visitor: &mut impl #name #ty_generics) {
+                    #(visitor.#visit_fns(&self.#field_names);)*
+                }
+
+                /// Visits each field mutably with the visitor in the order of the variants.
+                #vis fn accept_mut(&mut self, visitor: &mut impl #mut_name #ty_generics) {
+                    #(visitor.#visit_mut_fns(&mut self.#field_names);)*
+                }
+            }
+        },
+    ];
+
+    if let Some(accept_method) = accept_method {
+        items.push(syn::parse_quote! {
+            impl #enum_impl_generics #enum_ident #enum_ty_generics #enum_where_clause {
+                #accept_method
+            }
+        });
+    }
+
+    items
+}